@@ -0,0 +1,157 @@
+use alloc::string::{String, ToString};
+
+use chrono::FixedOffset;
+
+use crate::category::Category;
+use crate::entry::Entry;
+use crate::feed::Feed;
+use crate::link::Link;
+use crate::person::Person;
+use crate::source::Source;
+use crate::text::Text;
+use crate::util::FixedDateTime;
+
+impl Feed {
+    /// Bring this feed into a canonical form, so that feeds which differ only in the order of
+    /// their elements or in insignificant whitespace compare equal.
+    ///
+    /// Authors, contributors, categories and links are sorted, surrounding whitespace is
+    /// trimmed from the ID, text constructs, content and people, and dates are converted to
+    /// UTC. Entries are canonicalized like with `Entry::canonicalize` but keep their order, as
+    /// the order of entries is usually meaningful.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use atom_syndication::{Category, Feed, FixedDateTime};
+    ///
+    /// let category = |term: &str| Category { term: term.to_string(), ..Default::default() };
+    /// let mut a = Feed {
+    ///     title: " Title ".into(),
+    ///     updated: FixedDateTime::from_str("2024-01-01T02:00:00+02:00").unwrap(),
+    ///     categories: vec![category("b"), category("a")],
+    ///     ..Default::default()
+    /// };
+    /// let mut b = Feed {
+    ///     title: "Title".into(),
+    ///     updated: FixedDateTime::from_str("2024-01-01T00:00:00Z").unwrap(),
+    ///     categories: vec![category("a"), category("b")],
+    ///     ..Default::default()
+    /// };
+    /// assert_ne!(a, b);
+    ///
+    /// a.canonicalize();
+    /// b.canonicalize();
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn canonicalize(&mut self) {
+        canonical_text(&mut self.title);
+        canonical_string(&mut self.id);
+        canonical_datetime(&mut self.updated);
+        canonical_people(&mut self.authors);
+        canonical_categories(&mut self.categories);
+        canonical_people(&mut self.contributors);
+        canonical_links(&mut self.links);
+        self.rights.iter_mut().for_each(canonical_text);
+        self.subtitle.iter_mut().for_each(canonical_text);
+        self.entries.iter_mut().for_each(Entry::canonicalize);
+    }
+}
+
+impl Entry {
+    /// Bring this entry into a canonical form, as done for the entries of a feed by
+    /// `Feed::canonicalize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Person};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_id(" urn:a ");
+    /// entry.set_authors(vec![
+    ///     Person { name: "Jane".to_string(), ..Default::default() },
+    ///     Person { name: "Doe".to_string(), ..Default::default() },
+    /// ]);
+    /// entry.canonicalize();
+    /// assert_eq!(entry.id(), "urn:a");
+    /// assert_eq!(entry.authors()[0].name(), "Doe");
+    /// ```
+    pub fn canonicalize(&mut self) {
+        canonical_text(&mut self.title);
+        canonical_string(&mut self.id);
+        canonical_datetime(&mut self.updated);
+        canonical_people(&mut self.authors);
+        canonical_categories(&mut self.categories);
+        canonical_people(&mut self.contributors);
+        canonical_links(&mut self.links);
+        self.published.iter_mut().for_each(canonical_datetime);
+        self.rights.iter_mut().for_each(canonical_text);
+        self.source.iter_mut().for_each(canonical_source);
+        self.summary.iter_mut().for_each(canonical_text);
+        if let Some(value) = self.content.as_mut().and_then(|c| c.value.as_mut()) {
+            canonical_string(value);
+        }
+    }
+}
+
+fn canonical_source(source: &mut Source) {
+    canonical_text(&mut source.title);
+    canonical_string(&mut source.id);
+    canonical_datetime(&mut source.updated);
+    canonical_people(&mut source.authors);
+    canonical_categories(&mut source.categories);
+    canonical_people(&mut source.contributors);
+    canonical_links(&mut source.links);
+    source.rights.iter_mut().for_each(canonical_text);
+    source.subtitle.iter_mut().for_each(canonical_text);
+}
+
+fn canonical_string(value: &mut String) {
+    let trimmed = value.trim();
+    if trimmed.len() != value.len() {
+        *value = trimmed.to_string();
+    }
+}
+
+fn canonical_text(text: &mut Text) {
+    canonical_string(&mut text.value);
+    text.explicit_type = false;
+}
+
+fn canonical_datetime(datetime: &mut FixedDateTime) {
+    if let Some(utc) = FixedOffset::east_opt(0) {
+        *datetime = datetime.with_timezone(&utc);
+    }
+}
+
+fn canonical_people(people: &mut [Person]) {
+    for person in people.iter_mut() {
+        canonical_string(&mut person.name);
+        person.email.iter_mut().for_each(canonical_string);
+        person.uri.iter_mut().for_each(canonical_string);
+    }
+    people.sort();
+}
+
+fn canonical_categories(categories: &mut [Category]) {
+    categories.sort_by(|a, b| {
+        (&a.scheme, &a.term, &a.label, &a.base).cmp(&(&b.scheme, &b.term, &b.label, &b.base))
+    });
+}
+
+fn canonical_links(links: &mut [Link]) {
+    fn key(link: &Link) -> impl Ord + '_ {
+        (
+            &link.rel,
+            &link.href,
+            &link.hreflang,
+            &link.mime_type,
+            &link.title,
+            &link.length,
+            &link.base,
+        )
+    }
+    links.sort_by(|a, b| key(a).cmp(&key(b)));
+}
@@ -1,13 +1,26 @@
-use std::borrow::Cow;
-use std::io::{BufRead, Write};
+use alloc::string::{String, ToString};
+use core::fmt;
 
-use quick_xml::events::{BytesStart, Event};
-use quick_xml::Reader;
-use quick_xml::Writer;
+#[cfg(feature = "std")]
+use std::{
+    borrow::Cow,
+    io::{BufRead, Write},
+};
 
-use crate::error::{Error, XmlError};
-use crate::toxml::ToXml;
-use crate::util::{attr_value, decode};
+#[cfg(feature = "std")]
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader, Writer,
+};
+
+use crate::util::{join_base, resolve_uri};
+#[cfg(feature = "std")]
+use crate::{
+    error::{Error, XmlError},
+    feed::ParseOpts,
+    toxml::ToXml,
+    util::{attr_value, decode},
+};
 
 /// Represents a category in an Atom feed
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
@@ -28,6 +41,8 @@ pub struct Category {
     pub scheme: Option<String>,
     /// A human-readable label for display.
     pub label: Option<String>,
+    /// Base URL for resolving any relative references found in the element.
+    pub base: Option<String>,
 }
 
 impl Category {
@@ -78,6 +93,51 @@ impl Category {
         self.scheme.as_deref()
     }
 
+    /// Return the categorization scheme URI parsed as a `Url`.
+    ///
+    /// Returns `None` if it is not set. The stored string is left untouched if it fails to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Category;
+    ///
+    /// let mut category = Category::default();
+    /// category.set_scheme("http://example.com/scheme".to_string());
+    /// assert_eq!(category.scheme_url().unwrap().unwrap().as_str(), "http://example.com/scheme");
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn scheme_url(&self) -> Option<Result<url::Url, url::ParseError>> {
+        self.scheme.as_deref().map(url::Url::parse)
+    }
+
+    /// Return the categorization scheme URI resolved against the base URL in scope.
+    ///
+    /// The `xml:base` of the category itself is applied on top of `base`, as for
+    /// `Link::resolved_href`. Returns `None` if the scheme is not set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Category;
+    ///
+    /// let mut category = Category::default();
+    /// assert_eq!(category.resolved_scheme(Some("http://example.com/")), None);
+    ///
+    /// category.set_scheme("tags/".to_string());
+    /// assert_eq!(
+    ///     category.resolved_scheme(Some("http://example.com/feed.xml")),
+    ///     Some("http://example.com/tags/".to_string())
+    /// );
+    /// ```
+    pub fn resolved_scheme(&self, base: Option<&str>) -> Option<String> {
+        let scheme = self.scheme.as_deref()?;
+        Some(match join_base(base, self.base.as_deref()) {
+            Some(base) => resolve_uri(&base, scheme),
+            None => scheme.to_string(),
+        })
+    }
+
     /// Set the categorization scheme URI.
     ///
     /// # Examples
@@ -106,7 +166,6 @@ impl Category {
     /// category.set_scheme("Technology".to_string());
     /// assert_eq!(category.scheme(), Some("Technology"));
     /// ```
-
     pub fn label(&self) -> Option<&str> {
         self.label.as_deref()
     }
@@ -127,25 +186,74 @@ impl Category {
     {
         self.label = label.into();
     }
+
+    /// Return base URL of the category.
+    pub fn base(&self) -> Option<&str> {
+        self.base.as_deref()
+    }
+
+    /// Set base URL of the category.
+    pub fn set_base<V>(&mut self, base: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.base = base.into();
+    }
 }
 
+impl fmt::Display for Category {
+    /// Formats the category as its term, preceded by the scheme and `#` if there is a scheme,
+    /// and followed by the label in parentheses if there is a label.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Category;
+    ///
+    /// let mut category = Category::default();
+    /// category.set_term("rust");
+    /// assert_eq!(category.to_string(), "rust");
+    ///
+    /// category.set_label("Rust".to_string());
+    /// assert_eq!(category.to_string(), "rust (Rust)");
+    ///
+    /// category.set_scheme("http://example.com/tags".to_string());
+    /// assert_eq!(category.to_string(), "http://example.com/tags#rust (Rust)");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ref scheme) = self.scheme {
+            write!(f, "{}#", scheme)?;
+        }
+        f.write_str(&self.term)?;
+        if let Some(ref label) = self.label {
+            write!(f, " ({})", label)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
 impl Category {
     pub(crate) fn from_xml<'s, B: BufRead>(
         reader: &mut Reader<B>,
         element: &'s BytesStart<'s>,
+        opts: &ParseOpts,
     ) -> Result<Self, Error> {
         let mut category = Category::default();
 
         for att in element.attributes().with_checks(false).flatten() {
             match decode(att.key.as_ref(), reader)? {
                 Cow::Borrowed("term") => {
-                    category.term = attr_value(&att, reader)?.to_string();
+                    category.term = attr_value(&att, reader, opts)?.into_owned();
                 }
                 Cow::Borrowed("scheme") => {
-                    category.scheme = Some(attr_value(&att, reader)?.to_string());
+                    category.scheme = Some(attr_value(&att, reader, opts)?.into_owned());
                 }
                 Cow::Borrowed("label") => {
-                    category.label = Some(attr_value(&att, reader)?.to_string());
+                    category.label = Some(attr_value(&att, reader, opts)?.into_owned());
+                }
+                Cow::Borrowed("xml:base") => {
+                    category.base = Some(attr_value(&att, reader, opts)?.into_owned());
                 }
                 _ => {}
             }
@@ -154,9 +262,15 @@ impl Category {
     }
 }
 
+#[cfg(feature = "std")]
 impl ToXml for Category {
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
         let mut element = BytesStart::new("category");
+
+        if let Some(ref base) = self.base {
+            element.push_attribute(("xml:base", base.as_str()));
+        }
+
         element.push_attribute(("term", &*self.term));
 
         if let Some(ref scheme) = self.scheme {
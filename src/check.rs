@@ -0,0 +1,325 @@
+use std::borrow::Cow;
+
+use quick_xml::escape::resolve_xml_entity;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::category::Category;
+use crate::content::Content;
+use crate::entry::Entry;
+use crate::extension::{Extension, ExtensionMap};
+use crate::feed::{Feed, PrologItem};
+use crate::generator::Generator;
+use crate::link::Link;
+use crate::person::Person;
+use crate::source::Source;
+use crate::text::{Text, TextType};
+
+/// Checks that a value can be written as well-formed XML.
+///
+/// On failure the path of the offending field, such as `entry.author.name`, is returned.
+pub(crate) trait CheckXml {
+    fn check_xml(&self, path: &str) -> Result<(), String>;
+}
+
+impl<T: CheckXml> CheckXml for Option<T> {
+    fn check_xml(&self, path: &str) -> Result<(), String> {
+        match self {
+            Some(value) => value.check_xml(path),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: CheckXml> CheckXml for Vec<T> {
+    fn check_xml(&self, path: &str) -> Result<(), String> {
+        self.iter().try_for_each(|value| value.check_xml(path))
+    }
+}
+
+impl CheckXml for String {
+    fn check_xml(&self, path: &str) -> Result<(), String> {
+        check_chars(self, path)
+    }
+}
+
+fn field<'a>(path: &str, name: &'a str) -> Cow<'a, str> {
+    if path.is_empty() {
+        Cow::Borrowed(name)
+    } else {
+        Cow::Owned(format!("{}.{}", path, name))
+    }
+}
+
+/// Returns whether `c` matches the `Char` production of XML 1.0.
+fn is_xml_char(c: char) -> bool {
+    matches!(
+        c,
+        '\t' | '\n' | '\r' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}' | '\u{10000}'..='\u{10FFFF}'
+    )
+}
+
+fn check_chars(value: &str, path: &str) -> Result<(), String> {
+    if value.chars().all(is_xml_char) {
+        Ok(())
+    } else {
+        Err(path.to_string())
+    }
+}
+
+/// Checks a name written as an element or attribute name.
+fn check_name(name: &str, path: &str) -> Result<(), String> {
+    let mut chars = name.chars();
+    let valid = match chars.next() {
+        Some(c) => {
+            !c.is_ascii_digit()
+                && !matches!(c, '-' | '.')
+                && is_name_char(c)
+                && chars.all(is_name_char)
+        }
+        None => false,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(path.to_string())
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    is_xml_char(c)
+        && !c.is_whitespace()
+        && !matches!(c, '<' | '>' | '&' | '"' | '\'' | '=' | '/' | '?' | '!')
+}
+
+/// Checks markup which is written without escaping, such as xhtml text.
+fn check_markup(markup: &str, path: &str) -> Result<(), String> {
+    check_chars(markup, path)?;
+    let mut reader = Reader::from_str(markup);
+    let mut depth = 0usize;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(_)) => depth += 1,
+            Ok(Event::End(_)) if depth > 0 => depth -= 1,
+            Ok(Event::Text(text)) if text.unescape_with(resolve_xml_entity).is_ok() => {}
+            Ok(Event::Eof) if depth == 0 => return Ok(()),
+            Ok(Event::Empty(_))
+            | Ok(Event::CData(_))
+            | Ok(Event::Comment(_))
+            | Ok(Event::PI(_)) => {}
+            _ => return Err(path.to_string()),
+        }
+    }
+}
+
+/// Checks a comment, which must not contain `--` or end with `-`.
+fn check_comment(comment: &str, path: &str) -> Result<(), String> {
+    if comment.contains("--") || comment.ends_with('-') {
+        return Err(path.to_string());
+    }
+    check_chars(comment, path)
+}
+
+/// Checks a processing instruction, which must start with a target name that is not `xml`
+/// and must not contain `?>`.
+fn check_processing_instruction(pi: &str, path: &str) -> Result<(), String> {
+    let target = pi
+        .split(|c: char| c.is_ascii_whitespace())
+        .next()
+        .unwrap_or("");
+    if target.eq_ignore_ascii_case("xml") || pi.contains("?>") {
+        return Err(path.to_string());
+    }
+    check_name(target, path)?;
+    check_chars(pi, path)
+}
+
+impl CheckXml for Feed {
+    fn check_xml(&self, path: &str) -> Result<(), String> {
+        for item in &self.prolog {
+            let path = field(path, "prolog");
+            match item {
+                PrologItem::Comment(comment) => check_comment(comment, &path)?,
+                PrologItem::ProcessingInstruction(pi) => check_processing_instruction(pi, &path)?,
+            }
+        }
+        for (prefix, uri) in &self.namespaces {
+            check_name(prefix, &field(path, "namespaces"))?;
+            uri.check_xml(&field(path, "namespaces"))?;
+        }
+        self.base.check_xml(&field(path, "base"))?;
+        self.lang.check_xml(&field(path, "lang"))?;
+        self.xml_id.check_xml(&field(path, "xml_id"))?;
+        self.title.check_xml(&field(path, "title"))?;
+        self.id.check_xml(&field(path, "id"))?;
+        self.authors.check_xml(&field(path, "author"))?;
+        self.categories.check_xml(&field(path, "category"))?;
+        self.contributors.check_xml(&field(path, "contributor"))?;
+        self.generator.check_xml(&field(path, "generator"))?;
+        self.icon.check_xml(&field(path, "icon"))?;
+        self.links.check_xml(&field(path, "link"))?;
+        self.logo.check_xml(&field(path, "logo"))?;
+        self.rights.check_xml(&field(path, "rights"))?;
+        self.subtitle.check_xml(&field(path, "subtitle"))?;
+        check_extensions(&self.extensions, &field(path, "extensions"))?;
+        for comment in &self.comments {
+            check_comment(comment, &field(path, "comments"))?;
+        }
+        self.entries.check_xml(&field(path, "entry"))
+    }
+}
+
+impl CheckXml for Entry {
+    fn check_xml(&self, path: &str) -> Result<(), String> {
+        self.xml_id.check_xml(&field(path, "xml_id"))?;
+        self.base.check_xml(&field(path, "base"))?;
+        self.title.check_xml(&field(path, "title"))?;
+        self.id.check_xml(&field(path, "id"))?;
+        self.authors.check_xml(&field(path, "author"))?;
+        self.categories.check_xml(&field(path, "category"))?;
+        self.contributors.check_xml(&field(path, "contributor"))?;
+        self.links.check_xml(&field(path, "link"))?;
+        self.rights.check_xml(&field(path, "rights"))?;
+        self.source.check_xml(&field(path, "source"))?;
+        self.summary.check_xml(&field(path, "summary"))?;
+        self.content.check_xml(&field(path, "content"))?;
+        check_extensions(&self.extensions, &field(path, "extensions"))
+    }
+}
+
+impl CheckXml for Source {
+    fn check_xml(&self, path: &str) -> Result<(), String> {
+        self.title.check_xml(&field(path, "title"))?;
+        self.id.check_xml(&field(path, "id"))?;
+        self.authors.check_xml(&field(path, "author"))?;
+        self.categories.check_xml(&field(path, "category"))?;
+        self.contributors.check_xml(&field(path, "contributor"))?;
+        self.generator.check_xml(&field(path, "generator"))?;
+        self.icon.check_xml(&field(path, "icon"))?;
+        self.links.check_xml(&field(path, "link"))?;
+        self.logo.check_xml(&field(path, "logo"))?;
+        self.rights.check_xml(&field(path, "rights"))?;
+        self.subtitle.check_xml(&field(path, "subtitle"))
+    }
+}
+
+impl CheckXml for Text {
+    fn check_xml(&self, path: &str) -> Result<(), String> {
+        self.base.check_xml(&field(path, "base"))?;
+        self.lang.check_xml(&field(path, "lang"))?;
+        if self.r#type == TextType::Xhtml {
+            check_markup(&self.value, path)
+        } else {
+            self.value.check_xml(path)
+        }
+    }
+}
+
+impl CheckXml for Content {
+    fn check_xml(&self, path: &str) -> Result<(), String> {
+        self.base.check_xml(&field(path, "base"))?;
+        self.lang.check_xml(&field(path, "lang"))?;
+        self.content_type.check_xml(&field(path, "type"))?;
+        self.src.check_xml(&field(path, "src"))?;
+        match self.value {
+            Some(ref value) if self.content_type.as_deref() == Some("xhtml") => {
+                check_markup(value, path)
+            }
+            ref value => value.check_xml(path),
+        }
+    }
+}
+
+impl CheckXml for Person {
+    fn check_xml(&self, path: &str) -> Result<(), String> {
+        self.name.check_xml(&field(path, "name"))?;
+        self.email.check_xml(&field(path, "email"))?;
+        self.uri.check_xml(&field(path, "uri"))
+    }
+}
+
+impl CheckXml for Category {
+    fn check_xml(&self, path: &str) -> Result<(), String> {
+        self.term.check_xml(&field(path, "term"))?;
+        self.scheme.check_xml(&field(path, "scheme"))?;
+        self.label.check_xml(&field(path, "label"))?;
+        self.base.check_xml(&field(path, "base"))
+    }
+}
+
+impl CheckXml for Link {
+    fn check_xml(&self, path: &str) -> Result<(), String> {
+        self.href.check_xml(&field(path, "href"))?;
+        self.rel.check_xml(&field(path, "rel"))?;
+        self.hreflang.check_xml(&field(path, "hreflang"))?;
+        self.mime_type.check_xml(&field(path, "type"))?;
+        self.title.check_xml(&field(path, "title"))?;
+        self.length.check_xml(&field(path, "length"))?;
+        self.base.check_xml(&field(path, "base"))
+    }
+}
+
+impl CheckXml for Generator {
+    fn check_xml(&self, path: &str) -> Result<(), String> {
+        self.value.check_xml(path)?;
+        self.uri.check_xml(&field(path, "uri"))?;
+        self.version.check_xml(&field(path, "version"))
+    }
+}
+
+impl CheckXml for Extension {
+    fn check_xml(&self, path: &str) -> Result<(), String> {
+        let path = field(path, &self.name);
+        check_name(&self.name, &path)?;
+        for (name, value) in &self.attrs {
+            check_name(name, &path)?;
+            value.check_xml(&path)?;
+        }
+        self.value.check_xml(&path)?;
+        self.children
+            .values()
+            .try_for_each(|children| children.check_xml(&path))?;
+        self.ordered_children.check_xml(&path)
+    }
+}
+
+fn check_extensions(extensions: &ExtensionMap, path: &str) -> Result<(), String> {
+    extensions
+        .values()
+        .flat_map(|map| map.values())
+        .try_for_each(|extensions| extensions.check_xml(path))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check_markup() {
+        assert!(check_markup("<div>a<br/>&amp; b</div>", "").is_ok());
+        assert!(check_markup("<!-- comment -->", "").is_ok());
+        assert!(check_markup("<div>", "").is_err());
+        assert!(check_markup("</div>", "").is_err());
+        assert!(check_markup("<div></span>", "").is_err());
+        assert!(check_markup("a &nbsp; b", "").is_err());
+        assert!(check_markup("a\u{1}b", "").is_err());
+    }
+
+    #[test]
+    fn test_check_name() {
+        assert!(check_name("dc:creator", "").is_ok());
+        assert!(check_name("", "").is_err());
+        assert!(check_name("1a", "").is_err());
+        assert!(check_name("a b", "").is_err());
+        assert!(check_name("a<b", "").is_err());
+    }
+
+    #[test]
+    fn test_check_processing_instruction() {
+        assert!(check_processing_instruction(r#"xml-stylesheet href="a.xsl""#, "").is_ok());
+        assert!(check_processing_instruction("target", "").is_ok());
+        assert!(check_processing_instruction("", "").is_err());
+        assert!(check_processing_instruction("xml version=\"1.0\"", "").is_err());
+        assert!(check_processing_instruction("a ?> b", "").is_err());
+    }
+}
@@ -1,15 +1,30 @@
-use std::borrow::Cow;
-use std::io::{BufRead, Write};
-
-use quick_xml::events::attributes::Attributes;
-use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
-use quick_xml::Reader;
-use quick_xml::Writer;
-
-use crate::error::{Error, XmlError};
-use crate::fromxml::FromXml;
-use crate::toxml::ToXml;
-use crate::util::{atom_text, atom_xhtml, attr_value, decode};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use std::{
+    borrow::Cow,
+    io::{BufRead, Write},
+};
+
+#[cfg(feature = "std")]
+use quick_xml::{
+    events::{attributes::Attributes, BytesEnd, BytesStart, BytesText, Event},
+    Reader, Writer,
+};
+
+use crate::util::{decode_base64, decode_double_encoded, resolve_uri};
+#[cfg(feature = "std")]
+use crate::{
+    error::{Error, XmlError},
+    feed::ParseOpts,
+    fromxml::{FromEmptyXml, FromXml},
+    toxml::ToXml,
+    util::{
+        atom_html, atom_sniffed, atom_text_limited, atom_xhtml_limited, attr_value, decode,
+        join_base,
+    },
+};
 
 /// Represents the content of an Atom entry
 //
@@ -24,7 +39,7 @@ use crate::util::{atom_text, atom_xhtml, attr_value, decode};
 /// Setting of both fields when authoring an Atom feed is still technically possible,
 /// but it will lead to a non-compliant result.
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug, Default, Clone)]
 #[cfg_attr(feature = "builders", derive(Builder))]
 #[cfg_attr(
     feature = "builders",
@@ -37,14 +52,43 @@ use crate::util::{atom_text, atom_xhtml, attr_value, decode};
 pub struct Content {
     /// Base URL for resolving any relative references found in the element.
     pub base: Option<String>,
+    /// The base URL in scope of the element when it was read, which combines the `xml:base` of
+    /// the feed, the entry and the element itself.
+    ///
+    /// It is derived from the document, so it is neither written, serialized nor compared.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "builders", builder(setter(skip)))]
+    pub effective_base: Option<String>,
     /// Indicates the natural language for the element.
     pub lang: Option<String>,
     /// The text value of the content.
+    ///
+    /// For `xhtml` content this is the markup within the element, with text escaped. For other
+    /// types, child elements found in spite of the type, e.g. unescaped markup in `html`
+    /// content, are kept as tags around the unescaped text. In both cases tags are kept as they
+    /// appear in the document, so attribute values remain escaped and quoted as written. See
+    /// `ParseOpts::html_elements_as_xhtml` and `ParseOpts::sniff_xhtml_content` for reading
+    /// such content as markup instead.
     pub value: Option<String>,
     /// The URI of where the content can be found.
     pub src: Option<String>,
     /// Either "text", "html", "xhtml", or the MIME type of the content.
     pub content_type: Option<String>,
+    /// Whether the value was cut off when reading because it exceeded
+    /// `ParseOpts::max_content_len`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub truncated: bool,
+}
+
+impl PartialEq for Content {
+    fn eq(&self, other: &Self) -> bool {
+        self.base == other.base
+            && self.lang == other.lang
+            && self.value == other.value
+            && self.src == other.src
+            && self.content_type == other.content_type
+            && self.truncated == other.truncated
+    }
 }
 
 impl Content {
@@ -124,6 +168,126 @@ impl Content {
         self.src.as_deref()
     }
 
+    /// Return the URI where the content can be found, resolved against the effective base URL
+    /// of the element.
+    ///
+    /// The effective base combines the `xml:base` of the feed, the entry and the content
+    /// element, and is recorded when the feed is read. Without it, only the `xml:base` of the
+    /// content element is applied. Returns `None` if there is no `src`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// content.set_src("content.html".to_string());
+    /// assert_eq!(content.resolved_src().as_deref(), Some("content.html"));
+    ///
+    /// content.set_base("http://example.com/posts/".to_string());
+    /// assert_eq!(
+    ///     content.resolved_src().as_deref(),
+    ///     Some("http://example.com/posts/content.html")
+    /// );
+    /// ```
+    pub fn resolved_src(&self) -> Option<String> {
+        let src = self.src.as_deref()?;
+        let base = self.effective_base.as_deref().or(self.base.as_deref());
+        Some(match base {
+            Some(base) => resolve_uri(base, src),
+            None => src.to_string(),
+        })
+    }
+
+    /// Records the effective base URL of this element, given the base URL `parent` in scope
+    /// of it.
+    #[cfg(feature = "std")]
+    pub(crate) fn apply_base(&mut self, parent: Option<&str>) {
+        self.effective_base = join_base(parent, self.base.as_deref());
+    }
+
+    /// Return the URI where the content can be found parsed as a `Url`.
+    ///
+    /// Returns `None` if it is not set. The stored string is left untouched if it fails to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// content.set_src("http://example.com/content.html".to_string());
+    /// assert_eq!(content.src_url().unwrap().unwrap().as_str(), "http://example.com/content.html");
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn src_url(&self) -> Option<Result<url::Url, url::ParseError>> {
+        self.src.as_deref().map(url::Url::parse)
+    }
+
+    /// Return the scheme of the URI where the content can be found.
+    ///
+    /// Returns `None` if there is no `src` or it is a relative reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// content.set_src("file:///etc/passwd".to_string());
+    /// assert_eq!(content.src_scheme(), Some("file"));
+    ///
+    /// content.set_src("/content.html".to_string());
+    /// assert_eq!(content.src_scheme(), None);
+    /// ```
+    pub fn src_scheme(&self) -> Option<&str> {
+        let src = self.src.as_deref()?.trim_start();
+        let (scheme, _) = src.split_once(':')?;
+        let mut chars = scheme.chars();
+        let valid = matches!(chars.next(), Some(c) if c.is_ascii_alphabetic())
+            && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+        if valid {
+            Some(scheme)
+        } else {
+            None
+        }
+    }
+
+    /// Return whether the content can be found at an absolute `http` or `https` URL.
+    ///
+    /// Clients fetching external content can use this to refuse other schemes such as `file`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// content.set_src("https://example.com/content.html".to_string());
+    /// assert!(content.is_fetchable_http());
+    ///
+    /// content.set_src("file:///etc/passwd".to_string());
+    /// assert!(!content.is_fetchable_http());
+    ///
+    /// content.set_src("//example.com/content.html".to_string());
+    /// assert!(!content.is_fetchable_http());
+    /// ```
+    pub fn is_fetchable_http(&self) -> bool {
+        let scheme = match self.src_scheme() {
+            Some(scheme) => scheme,
+            None => return false,
+        };
+        if !scheme.eq_ignore_ascii_case("http") && !scheme.eq_ignore_ascii_case("https") {
+            return false;
+        }
+        let src = self.src.as_deref().unwrap_or_default().trim_start();
+        let rest = &src[scheme.len() + 1..];
+        let authority = rest
+            .strip_prefix("//")
+            .and_then(|rest| rest.split(['/', '?', '#']).next());
+        matches!(authority, Some(authority) if !authority.is_empty())
+    }
+
     /// Set the URI where the content can be found.
     ///
     /// # Examples
@@ -175,42 +339,239 @@ impl Content {
     {
         self.content_type = content_type.into();
     }
+
+    /// Return the body of the content according to its type.
+    ///
+    /// Content with a `src` is external. Otherwise the value is returned as text for `"text"`
+    /// and other textual or XML MIME types, as markup for `"html"` and `"xhtml"`, or decoded
+    /// from base64 for any other MIME type. Parameters of MIME types, such as `charset`, are
+    /// ignored. Returns `None` if there is neither a value nor a
+    /// `src`, or if a binary value is not valid base64.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Content, ContentBody};
+    ///
+    /// let mut content = Content::default();
+    /// content.set_content_type("html".to_string());
+    /// content.set_value("<p>Hello</p>".to_string());
+    /// assert_eq!(content.body(), Some(ContentBody::Html("<p>Hello</p>")));
+    ///
+    /// content.set_content_type("application/octet-stream".to_string());
+    /// content.set_value("SGVsbG8=".to_string());
+    /// assert_eq!(content.body(), Some(ContentBody::Binary(b"Hello".to_vec())));
+    ///
+    /// content.set_content_type("video/mp4".to_string());
+    /// content.set_src("http://example.com/video.mp4".to_string());
+    /// assert_eq!(
+    ///     content.body(),
+    ///     Some(ContentBody::External { src: "http://example.com/video.mp4", mime: Some("video/mp4") })
+    /// );
+    /// ```
+    pub fn body(&self) -> Option<ContentBody<'_>> {
+        if let Some(ref src) = self.src {
+            return Some(ContentBody::External {
+                src,
+                mime: self.content_type.as_deref(),
+            });
+        }
+        let value = self.value.as_deref()?;
+        if self.is_binary() {
+            return decode_base64(value).map(ContentBody::Binary);
+        }
+        Some(match self.content_type.as_deref().map(mime_essence) {
+            Some("html") | Some("text/html") => ContentBody::Html(value),
+            Some("xhtml") | Some("application/xhtml+xml") => ContentBody::Xhtml(value),
+            _ => ContentBody::Text(value),
+        })
+    }
+
+    /// Remove a second layer of escaping from the value of HTML content, and return whether
+    /// it was found.
+    ///
+    /// Some generators escape markup twice, so that `<p>` is read as `&lt;p&gt;` and would be
+    /// shown as such. HTML content without any `<` but with an escaped `&lt;` is unescaped
+    /// once. Other content is left unchanged, as is HTML content which does not look
+    /// double-encoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// content.set_content_type("html".to_string());
+    /// content.set_value("&lt;p&gt;Hello&lt;/p&gt;".to_string());
+    /// assert!(content.decode_once_more());
+    /// assert_eq!(content.value(), Some("<p>Hello</p>"));
+    /// ```
+    pub fn decode_once_more(&mut self) -> bool {
+        if !matches!(
+            self.content_type.as_deref().map(mime_essence),
+            Some("html") | Some("text/html")
+        ) {
+            return false;
+        }
+        match self.value.as_deref().and_then(decode_double_encoded) {
+            Some(value) => {
+                self.value = Some(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Return whether the value of this content is base64-encoded binary data.
+    ///
+    /// This is the case for inline content whose type is a MIME type other than `text/*` and
+    /// XML types, such as `"image/png"`. The value is kept as read, `body` decodes it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// content.set_content_type("application/pdf".to_string());
+    /// assert!(content.is_binary());
+    ///
+    /// content.set_content_type("text/plain".to_string());
+    /// assert!(!content.is_binary());
+    ///
+    /// content.set_content_type("html".to_string());
+    /// assert!(!content.is_binary());
+    /// ```
+    pub fn is_binary(&self) -> bool {
+        if self.src.is_some() {
+            return false;
+        }
+        match self.content_type.as_deref() {
+            None | Some("text") | Some("html") | Some("xhtml") => false,
+            Some(mime) => !is_textual_mime(mime),
+        }
+    }
+
+    /// Return the value of `"html"` or `"xhtml"` content with scripts, event handlers and other
+    /// markup which is unsafe to render removed, using the default allowlist of `ammonia`.
+    ///
+    /// Returns `None` for other types and for content with a `src`, as classified by `body`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Content;
+    ///
+    /// let mut content = Content::default();
+    /// content.set_content_type("html".to_string());
+    /// content.set_value(r#"<p><a href="javascript:steal()">Link</a></p><script>steal()</script>"#.to_string());
+    /// assert_eq!(content.sanitized_html().as_deref(), Some("<p><a rel=\"noopener noreferrer\">Link</a></p>"));
+    ///
+    /// content.set_content_type("text".to_string());
+    /// assert_eq!(content.sanitized_html(), None);
+    /// ```
+    #[cfg(feature = "sanitize")]
+    pub fn sanitized_html(&self) -> Option<String> {
+        match self.body()? {
+            ContentBody::Html(value) | ContentBody::Xhtml(value) => Some(ammonia::clean(value)),
+            _ => None,
+        }
+    }
+}
+
+/// Whether content of the MIME type is stored as text rather than base64.
+fn is_textual_mime(mime: &str) -> bool {
+    let mime = mime_essence(mime);
+    mime.starts_with("text/") || mime.ends_with("+xml") || mime.ends_with("/xml")
+}
+
+/// Returns the MIME type without any parameters, e.g. `text/html` for
+/// `text/html; charset=utf-8`.
+fn mime_essence(mime: &str) -> &str {
+    mime.split(';').next().unwrap_or_default().trim()
+}
+
+/// The body of a [`Content`] as returned by [`Content::body`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentBody<'a> {
+    /// Plain text.
+    Text(&'a str),
+    /// HTML markup.
+    Html(&'a str),
+    /// XHTML markup.
+    Xhtml(&'a str),
+    /// Decoded binary data.
+    Binary(Vec<u8>),
+    /// Content which is referenced by a URI.
+    External {
+        /// The URI of the content.
+        src: &'a str,
+        /// The MIME type of the content, if given.
+        mime: Option<&'a str>,
+    },
 }
 
+#[cfg(feature = "std")]
 impl FromXml for Content {
     fn from_xml<B: BufRead>(
+        reader: &mut Reader<B>,
+        atts: Attributes<'_>,
+        opts: &ParseOpts,
+    ) -> Result<Self, Error> {
+        let mut content = Content::from_empty_xml(reader, atts, opts)?;
+
+        let limit = opts.max_content_len;
+        let (value, truncated) = match content.content_type.as_deref() {
+            Some("xhtml") => atom_xhtml_limited(reader, opts, limit)?,
+            None | Some("text") if opts.sniff_xhtml_content && content.src.is_none() => {
+                let (value, is_xhtml, truncated) = atom_sniffed(reader, opts, limit)?;
+                if is_xhtml {
+                    content.content_type = Some("xhtml".to_string());
+                }
+                (value, truncated)
+            }
+            Some("html") if opts.html_elements_as_xhtml => atom_html(reader, opts, limit)?,
+            _ => atom_text_limited(reader, opts, limit)?,
+        };
+        content.value = value;
+        content.truncated = truncated;
+
+        Ok(content)
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromEmptyXml for Content {
+    fn from_empty_xml<B: BufRead>(
         reader: &mut Reader<B>,
         mut atts: Attributes<'_>,
+        opts: &ParseOpts,
     ) -> Result<Self, Error> {
         let mut content = Content::default();
 
         for att in atts.with_checks(false).flatten() {
             match decode(att.key.as_ref(), reader)? {
                 Cow::Borrowed("xml:base") => {
-                    content.base = Some(attr_value(&att, reader)?.to_string());
+                    content.base = Some(attr_value(&att, reader, opts)?.into_owned());
                 }
                 Cow::Borrowed("xml:lang") => {
-                    content.lang = Some(attr_value(&att, reader)?.to_string());
+                    content.lang = Some(attr_value(&att, reader, opts)?.into_owned());
                 }
                 Cow::Borrowed("type") => {
-                    content.content_type = Some(attr_value(&att, reader)?.to_string());
+                    content.content_type = Some(attr_value(&att, reader, opts)?.into_owned());
                 }
                 Cow::Borrowed("src") => {
-                    content.src = Some(attr_value(&att, reader)?.to_string());
+                    content.src = Some(attr_value(&att, reader, opts)?.into_owned());
                 }
                 _ => {}
             }
         }
 
-        content.value = match content.content_type {
-            Some(ref t) if t == "xhtml" => atom_xhtml(reader)?,
-            _ => atom_text(reader)?,
-        };
-
         Ok(content)
     }
 }
 
+#[cfg(feature = "std")]
 impl ToXml for Content {
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
         let name = "content";
@@ -268,7 +629,7 @@ impl ContentBuilder {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test {
     use super::*;
     use crate::error::Error;
@@ -289,15 +650,18 @@ mod test {
     }
 
     fn from_xml(xml: &str) -> Result<Content, Error> {
+        from_xml_with_opts(xml, &ParseOpts::default())
+    }
+
+    fn from_xml_with_opts(xml: &str, opts: &ParseOpts) -> Result<Content, Error> {
         let mut reader = Reader::from_reader(xml.as_bytes());
-        reader.config_mut().expand_empty_elements = true;
 
         loop {
             let mut buf = Vec::new();
             match reader.read_event_into(&mut buf).map_err(XmlError::new)? {
                 Event::Start(element) => {
                     if decode(element.name().as_ref(), &reader)? == "content" {
-                        let content = Content::from_xml(&mut reader, element.attributes())?;
+                        let content = Content::from_xml(&mut reader, element.attributes(), opts)?;
                         return Ok(content);
                     } else {
                         return Err(Error::InvalidStartTag);
@@ -362,4 +726,45 @@ mod test {
             )
         );
     }
+
+    #[test]
+    fn test_sniff_xhtml() {
+        let opts = ParseOpts {
+            sniff_xhtml_content: true,
+            ..Default::default()
+        };
+        let xml_fragment = r#"<content>
+            <div>a line<br/>&amp; one more</div>
+        </content>"#;
+        let content = from_xml_with_opts(xml_fragment, &opts).unwrap();
+        assert_eq!(content.content_type(), Some("xhtml"));
+        assert_eq!(
+            content.value(),
+            Some("<div>a line<br/>&amp; one more</div>")
+        );
+
+        let content = from_xml(xml_fragment).unwrap();
+        assert_eq!(content.content_type(), None);
+        assert_eq!(
+            content.value().map(str::trim),
+            Some("<div>a line<br/>& one more</div>")
+        );
+    }
+
+    #[test]
+    fn test_sniff_text() {
+        let opts = ParseOpts {
+            sniff_xhtml_content: true,
+            ..Default::default()
+        };
+        for xml_fragment in [
+            r#"<content>Text with <b>bold</b> word</content>"#,
+            r#"<content><p>one</p><p>two</p></content>"#,
+            r#"<content type="html">&lt;div&gt;markup&lt;/div&gt;</content>"#,
+            r#"<content src="http://example.com/"><div>ignored</div></content>"#,
+        ] {
+            let content = from_xml_with_opts(xml_fragment, &opts).unwrap();
+            assert_eq!(content, from_xml(xml_fragment).unwrap());
+        }
+    }
 }
@@ -1,23 +1,44 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
 use std::io::{BufRead, Write};
 
-use quick_xml::events::attributes::Attributes;
-use quick_xml::events::{BytesEnd, BytesStart, Event};
-use quick_xml::Reader;
-use quick_xml::Writer;
+#[cfg(feature = "std")]
+use quick_xml::{
+    events::{attributes::Attributes, BytesEnd, BytesStart, Event},
+    Reader, Writer,
+};
 
 use crate::category::Category;
-use crate::content::Content;
-use crate::error::{Error, XmlError};
-use crate::extension::util::{extension_name, parse_extension};
+use crate::content::{Content, ContentBody};
+#[cfg(feature = "builders")]
+use crate::extension::util::{merge_extensions, simple_extension};
 use crate::extension::ExtensionMap;
-use crate::fromxml::FromXml;
-use crate::link::Link;
+use crate::feed::Feed;
+use crate::link::{Link, LinkRel};
 use crate::person::Person;
 use crate::source::Source;
 use crate::text::Text;
-use crate::toxml::{ToXml, WriterExt};
-use crate::util::{atom_datetime, atom_text, decode, default_fixed_datetime, skip, FixedDateTime};
+#[cfg(feature = "builders")]
+use crate::util::parse_rfc3339;
+use crate::util::{
+    default_fixed_datetime, normalize_id, stable_hash, FixedDateTime, TRACKING_PARAMS,
+};
+#[cfg(feature = "std")]
+use crate::{
+    error::{Error, XmlError},
+    extension::util::{extension_name, parse_extension},
+    feed::{ParseOpts, WriteConfig},
+    fromxml::{FromEmptyXml, FromXml},
+    toxml::{ToXml, WriterExt},
+    util::{
+        attr_value, check_duplicate, decode, element_datetime, element_text, format_datetime,
+        join_base, now, skip,
+    },
+};
 
 /// Represents an entry in an Atom feed
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
@@ -48,6 +69,8 @@ pub struct Entry {
     #[cfg_attr(feature = "builders", builder(setter(each = "contributor")))]
     pub contributors: Vec<Person>,
     /// The Web pages related to the entry.
+    ///
+    /// Links are read and written in document order.
     #[cfg_attr(feature = "builders", builder(setter(each = "link")))]
     pub links: Vec<Link>,
     /// The time of the initial creation or first availability of the entry.
@@ -63,9 +86,48 @@ pub struct Entry {
     /// The extensions for this entry.
     #[cfg_attr(feature = "builders", builder(setter(each = "extension")))]
     pub extensions: ExtensionMap,
+    /// The `xml:id` of the element, for references from elsewhere in the document.
+    pub xml_id: Option<String>,
+    /// Base URL for resolving any relative references found in the element.
+    pub base: Option<String>,
 }
 
 impl Entry {
+    /// Creates an entry with the elements required by RFC 4287: a unique id, a title and the
+    /// last time it was modified.
+    ///
+    /// `Default` remains available for building an entry step by step, but leaves the id empty
+    /// and the date at the Unix epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, FixedDateTime};
+    /// use std::str::FromStr;
+    ///
+    /// let entry = Entry::new(
+    ///     "urn:uuid:60a76c80-d399-11d9-b93c-0003939e0af6",
+    ///     "Entry Title",
+    ///     FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap(),
+    /// );
+    /// assert_eq!(entry.id(), "urn:uuid:60a76c80-d399-11d9-b93c-0003939e0af6");
+    /// assert_eq!(entry.title(), "Entry Title");
+    /// assert_eq!(entry.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    /// ```
+    pub fn new<I, T, U>(id: I, title: T, updated: U) -> Self
+    where
+        I: Into<String>,
+        T: Into<Text>,
+        U: Into<FixedDateTime>,
+    {
+        Entry {
+            id: id.into(),
+            title: title.into(),
+            updated: updated.into(),
+            ..Default::default()
+        }
+    }
+
     /// Return the title of this entry.
     ///
     /// # Examples
@@ -130,6 +192,87 @@ impl Entry {
         self.id = id.into();
     }
 
+    /// Return a normalized form of the ID of this entry, suitable as a key for deduplicating
+    /// entries across fetches.
+    ///
+    /// Surrounding whitespace is trimmed and the scheme is lowercased. For URLs the host is
+    /// lowercased as well and common tracking query parameters such as `utm_source` are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_id(" HTTPS://Example.com/posts/1?utm_source=rss ");
+    /// assert_eq!(entry.dedup_key(), "https://example.com/posts/1");
+    /// ```
+    pub fn dedup_key(&self) -> Cow<'_, str> {
+        normalize_id(&self.id, TRACKING_PARAMS)
+    }
+
+    /// Return a normalized form of the ID of this entry, removing the given query parameters
+    /// instead of the default tracking parameters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_id("https://example.com/posts/1?ref=home&page=2");
+    /// assert_eq!(entry.dedup_key_with(&["ref"]), "https://example.com/posts/1?page=2");
+    /// ```
+    pub fn dedup_key_with(&self, tracking_params: &[&str]) -> Cow<'_, str> {
+        normalize_id(&self.id, tracking_params)
+    }
+
+    /// Set an ID for this entry if it has none, and return the ID.
+    ///
+    /// An entry whose ID is empty or only whitespace gets the `href` of its first alternate
+    /// link. Without such a link the ID is `urn:hash:` followed by a hash of the title, summary
+    /// and content of the entry, which stays the same for the same entry across fetches and
+    /// versions of this crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_links(vec![Link { href: "http://example.com/1".into(), ..Default::default() }]);
+    /// assert_eq!(entry.ensure_id(), "http://example.com/1");
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_title("Entry Title");
+    /// assert!(entry.ensure_id().starts_with("urn:hash:"));
+    /// ```
+    pub fn ensure_id(&mut self) -> &str {
+        if self.id.trim().is_empty() {
+            let href = self
+                .links_by_rel(LinkRel::Alternate)
+                .next()
+                .map(|link| link.href.trim())
+                .filter(|href| !href.is_empty())
+                .map(String::from);
+            self.id = match href {
+                Some(href) => href,
+                None => {
+                    let hash = stable_hash([
+                        self.title.value.as_str(),
+                        self.summary.as_ref().map_or("", |summary| &summary.value),
+                        self.content
+                            .as_ref()
+                            .and_then(|content| content.value.as_deref())
+                            .unwrap_or_default(),
+                    ]);
+                    format!("urn:hash:{:016x}", hash)
+                }
+            };
+        }
+        self.id.as_str()
+    }
+
     /// Return the last time that this entry was modified.
     ///
     /// # Examples
@@ -147,6 +290,23 @@ impl Entry {
         &self.updated
     }
 
+    /// Return the last time that this entry was modified as seconds since the Unix epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    /// use atom_syndication::FixedDateTime;
+    /// use std::str::FromStr;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_updated(FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap());
+    /// assert_eq!(entry.updated_timestamp(), 1496520944);
+    /// ```
+    pub fn updated_timestamp(&self) -> i64 {
+        self.updated.timestamp()
+    }
+
     /// Set the last time that this entry was modified.
     ///
     /// # Examples
@@ -166,6 +326,49 @@ impl Entry {
         self.updated = updated.into();
     }
 
+    /// Set the last time that this entry was modified to the current time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_updated_now();
+    /// assert!(entry.updated().timestamp() > 0);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn set_updated_now(&mut self) {
+        self.updated = now();
+    }
+
+    /// Set the last time that this entry was modified to the time it was published if the
+    /// former is missing.
+    ///
+    /// A missing `<updated>` element is read as the Unix epoch, which is what this method
+    /// checks for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    /// use atom_syndication::FixedDateTime;
+    /// use std::str::FromStr;
+    ///
+    /// let published = FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap();
+    /// let mut entry = Entry::default();
+    /// entry.set_published(published);
+    /// entry.fill_missing_dates();
+    /// assert_eq!(entry.updated(), &published);
+    /// ```
+    pub fn fill_missing_dates(&mut self) {
+        if self.updated == default_fixed_datetime() {
+            if let Some(published) = self.published {
+                self.updated = published;
+            }
+        }
+    }
+
     /// Return the authors of this entry.
     ///
     /// # Examples
@@ -181,6 +384,21 @@ impl Entry {
         self.authors.as_slice()
     }
 
+    /// Return a mutable reference to the authors of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Person};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.authors_mut().push(Person::default());
+    /// assert_eq!(entry.authors().len(), 1);
+    /// ```
+    pub fn authors_mut(&mut self) -> &mut Vec<Person> {
+        &mut self.authors
+    }
+
     /// Set the authors of this entry.
     ///
     /// # Examples
@@ -198,6 +416,35 @@ impl Entry {
         self.authors = authors.into();
     }
 
+    /// Return the authors which apply to this entry within the given feed.
+    ///
+    /// As defined by RFC 4287, these are the authors of the entry itself or, if it has none,
+    /// those of its `source` and then those of the containing feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed, Person};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_authors(vec![Person { name: "John Doe".to_string(), ..Default::default() }]);
+    ///
+    /// let mut entry = Entry::default();
+    /// assert_eq!(entry.effective_authors(&feed)[0].name(), "John Doe");
+    ///
+    /// entry.set_authors(vec![Person { name: "Jane Doe".to_string(), ..Default::default() }]);
+    /// assert_eq!(entry.effective_authors(&feed)[0].name(), "Jane Doe");
+    /// ```
+    pub fn effective_authors<'a>(&'a self, feed: &'a Feed) -> &'a [Person] {
+        if !self.authors.is_empty() {
+            &self.authors
+        } else if let Some(source) = self.source.as_ref().filter(|s| !s.authors.is_empty()) {
+            &source.authors
+        } else {
+            &feed.authors
+        }
+    }
+
     /// Return the categories this entry belongs to.
     ///
     /// # Examples
@@ -213,6 +460,21 @@ impl Entry {
         self.categories.as_slice()
     }
 
+    /// Return a mutable reference to the categories this entry belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Category};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.categories_mut().push(Category::default());
+    /// assert_eq!(entry.categories().len(), 1);
+    /// ```
+    pub fn categories_mut(&mut self) -> &mut Vec<Category> {
+        &mut self.categories
+    }
+
     /// Set the categories this entry belongs to.
     ///
     /// # Examples
@@ -230,6 +492,33 @@ impl Entry {
         self.categories = categories.into();
     }
 
+    /// Return whether this entry belongs to the category `term`.
+    ///
+    /// With a `scheme`, only categories of that scheme match. Without one, the scheme of the
+    /// categories is ignored. Terms and schemes are compared exactly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Category, Entry};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_categories(vec![Category {
+    ///     term: "rust".to_string(),
+    ///     scheme: Some("http://example.com/tags".to_string()),
+    ///     ..Default::default()
+    /// }]);
+    /// assert!(entry.has_category("rust", None));
+    /// assert!(entry.has_category("rust", Some("http://example.com/tags")));
+    /// assert!(!entry.has_category("rust", Some("http://example.com/topics")));
+    /// assert!(!entry.has_category("go", None));
+    /// ```
+    pub fn has_category(&self, term: &str, scheme: Option<&str>) -> bool {
+        self.categories.iter().any(|category| {
+            category.term == term && (scheme.is_none() || category.scheme.as_deref() == scheme)
+        })
+    }
+
     /// Return the contributors to this entry.
     ///
     /// # Examples
@@ -245,6 +534,21 @@ impl Entry {
         self.contributors.as_slice()
     }
 
+    /// Return a mutable reference to the contributors to this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Person};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.contributors_mut().push(Person::default());
+    /// assert_eq!(entry.contributors().len(), 1);
+    /// ```
+    pub fn contributors_mut(&mut self) -> &mut Vec<Person> {
+        &mut self.contributors
+    }
+
     /// Set the contributors to this entry.
     ///
     /// # Examples
@@ -277,6 +581,22 @@ impl Entry {
         self.links.as_slice()
     }
 
+    /// Return a mutable reference to the links for this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_links(vec![Link::default()]);
+    /// entry.links_mut()[0].set_href("http://example.com/");
+    /// assert_eq!(entry.links()[0].href(), "http://example.com/");
+    /// ```
+    pub fn links_mut(&mut self) -> &mut Vec<Link> {
+        &mut self.links
+    }
+
     /// Set the links for this entry.
     ///
     /// # Examples
@@ -294,6 +614,135 @@ impl Entry {
         self.links = links.into();
     }
 
+    /// Return the links of this entry with the given relation, in document order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link, LinkRel};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_links(vec![
+    ///     Link { rel: "edit".into(), href: "http://example.com/edit".into(), ..Default::default() },
+    ///     Link { href: "http://example.com/".into(), ..Default::default() },
+    /// ]);
+    /// let hrefs = entry.links_by_rel(LinkRel::Edit).map(Link::href).collect::<Vec<_>>();
+    /// assert_eq!(hrefs, vec!["http://example.com/edit"]);
+    /// ```
+    pub fn links_by_rel(&self, rel: LinkRel) -> impl Iterator<Item = &Link> {
+        self.links.iter().filter(move |link| link.relation() == rel)
+    }
+
+    /// Return the first link of this entry with `rel="license"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link, LinkRel};
+    ///
+    /// let mut entry = Entry::default();
+    /// assert!(entry.license_link().is_none());
+    ///
+    /// entry.set_links(vec![Link {
+    ///     rel: LinkRel::License.into(),
+    ///     href: "https://creativecommons.org/licenses/by/4.0/".into(),
+    ///     ..Default::default()
+    /// }]);
+    /// assert_eq!(entry.license_link().map(Link::href), Some("https://creativecommons.org/licenses/by/4.0/"));
+    /// ```
+    pub fn license_link(&self) -> Option<&Link> {
+        self.links_by_rel(LinkRel::License).next()
+    }
+
+    /// Return the alternate link of this entry whose `hreflang` best matches the given language
+    /// tag.
+    ///
+    /// Language tags are compared case-insensitively by their subtags, so for `"en-US"` a link
+    /// with `hreflang="en-US"` is preferred over one with `"en"`, which is preferred over one
+    /// with `"en-GB"` as its tag diverges from the requested one. Without a link in the language,
+    /// the first alternate link without an `hreflang` is returned. Among equally good links the
+    /// first one is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let link = |href: &str, hreflang: Option<&str>| Link {
+    ///     href: href.into(),
+    ///     hreflang: hreflang.map(Into::into),
+    ///     ..Default::default()
+    /// };
+    /// let mut entry = Entry::default();
+    /// entry.set_links(vec![
+    ///     link("http://example.com/", None),
+    ///     link("http://example.com/de", Some("de")),
+    ///     link("http://example.com/en-gb", Some("en-GB")),
+    ///     link("http://example.com/en", Some("en")),
+    /// ]);
+    /// let href = |lang| entry.alternate_link_for_lang(lang).map(Link::href);
+    /// assert_eq!(href("en-US"), Some("http://example.com/en"));
+    /// assert_eq!(href("en-gb"), Some("http://example.com/en-gb"));
+    /// assert_eq!(href("DE"), Some("http://example.com/de"));
+    /// assert_eq!(href("fr"), Some("http://example.com/"));
+    /// ```
+    pub fn alternate_link_for_lang(&self, lang: &str) -> Option<&Link> {
+        let mut best: Option<(&Link, (usize, bool))> = None;
+        for link in self.links_by_rel(LinkRel::Alternate) {
+            let score = match link.hreflang {
+                Some(ref hreflang) => match matching_subtags(hreflang, lang) {
+                    (0, _) => continue,
+                    matching => matching,
+                },
+                None => (0, false),
+            };
+            if !matches!(best, Some((_, best)) if best >= score) {
+                best = Some((link, score));
+            }
+        }
+        best.map(|(link, _)| link)
+    }
+
+    /// Return the links of this entry with `rel="enclosure"`, in document order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_links(vec![
+    ///     Link { rel: "enclosure".into(), href: "http://example.com/audio.mp3".into(), ..Default::default() },
+    ///     Link { rel: "alternate".into(), href: "http://example.com/".into(), ..Default::default() },
+    ///     Link { rel: "enclosure".into(), href: "http://example.com/audio.ogg".into(), ..Default::default() },
+    /// ]);
+    /// let hrefs = entry.enclosures().map(Link::href).collect::<Vec<_>>();
+    /// assert_eq!(hrefs, vec!["http://example.com/audio.mp3", "http://example.com/audio.ogg"]);
+    /// ```
+    pub fn enclosures(&self) -> impl Iterator<Item = &Link> {
+        self.links_by_rel(LinkRel::Enclosure)
+    }
+
+    /// Return the first link of this entry with `rel="enclosure"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Link};
+    ///
+    /// let mut entry = Entry::default();
+    /// assert!(entry.primary_enclosure().is_none());
+    ///
+    /// entry.set_links(vec![
+    ///     Link { rel: "enclosure".into(), href: "http://example.com/audio.mp3".into(), ..Default::default() },
+    ///     Link { rel: "enclosure".into(), href: "http://example.com/audio.ogg".into(), ..Default::default() },
+    /// ]);
+    /// assert_eq!(entry.primary_enclosure().map(Link::href), Some("http://example.com/audio.mp3"));
+    /// ```
+    pub fn primary_enclosure(&self) -> Option<&Link> {
+        self.enclosures().next()
+    }
+
     /// Return the time that this entry was initially created or first made available.
     ///
     /// # Examples
@@ -311,6 +760,25 @@ impl Entry {
         self.published.as_ref()
     }
 
+    /// Return the time that this entry was initially created or first made available as
+    /// seconds since the Unix epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    /// use atom_syndication::FixedDateTime;
+    /// use std::str::FromStr;
+    ///
+    /// let mut entry = Entry::default();
+    /// assert_eq!(entry.published_timestamp(), None);
+    /// entry.set_published(FixedDateTime::from_str("2017-06-01T15:15:44-05:00").unwrap());
+    /// assert_eq!(entry.published_timestamp(), Some(1496348144));
+    /// ```
+    pub fn published_timestamp(&self) -> Option<i64> {
+        self.published.map(|published| published.timestamp())
+    }
+
     /// Set the time that this entry was initially created or first made available.
     ///
     /// # Examples
@@ -459,6 +927,62 @@ impl Entry {
         self.content = content.into();
     }
 
+    /// Return the body best suited for display, preferring the content over the summary.
+    ///
+    /// The content is classified by `Content::body`: HTML and XHTML are returned as HTML, while
+    /// "text" and other textual or XML MIME types are returned as text. Content which is only
+    /// referenced by its `src` attribute is returned as an external link if no summary is
+    /// present. Binary content is skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Content, DisplayBody, Entry, Text};
+    ///
+    /// let mut entry = Entry::default();
+    /// assert_eq!(entry.display_body(), None);
+    ///
+    /// entry.set_summary(Text::plain("Summary"));
+    /// assert_eq!(entry.display_body(), Some(DisplayBody::Text("Summary".to_string())));
+    ///
+    /// let mut content = Content::default();
+    /// content.set_content_type("html".to_string());
+    /// content.set_value("<p>Content</p>".to_string());
+    /// entry.set_content(content);
+    /// assert_eq!(entry.display_body(), Some(DisplayBody::Html("<p>Content</p>".to_string())));
+    ///
+    /// let mut content = Content::default();
+    /// content.set_content_type("text/html; charset=utf-8".to_string());
+    /// content.set_value("<p>Content</p>".to_string());
+    /// entry.set_content(content);
+    /// assert_eq!(entry.display_body(), Some(DisplayBody::Html("<p>Content</p>".to_string())));
+    /// ```
+    pub fn display_body(&self) -> Option<DisplayBody> {
+        let body = self.content.as_ref().and_then(Content::body);
+        match body {
+            Some(ContentBody::Text(value)) => Some(DisplayBody::Text(value.to_string())),
+            Some(ContentBody::Html(value)) | Some(ContentBody::Xhtml(value)) => {
+                Some(DisplayBody::Html(value.to_string()))
+            }
+            Some(ContentBody::Binary(_)) | Some(ContentBody::External { .. }) | None => None,
+        }
+        .or_else(|| {
+            self.summary.as_ref().map(|summary| {
+                if summary.r#type.is_markup() {
+                    DisplayBody::Html(summary.value.clone())
+                } else {
+                    DisplayBody::Text(summary.value.clone())
+                }
+            })
+        })
+        .or_else(|| match body {
+            Some(ContentBody::External { src, .. }) => {
+                Some(DisplayBody::ExternalLink(src.to_string()))
+            }
+            _ => None,
+        })
+    }
+
     /// Return the extensions for this entry.
     ///
     /// # Examples
@@ -505,61 +1029,231 @@ impl Entry {
     {
         self.extensions = extensions.into()
     }
+
+    /// Return the `xml:id` of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_xml_id("entry-1".to_string());
+    /// assert_eq!(entry.xml_id(), Some("entry-1"));
+    /// ```
+    pub fn xml_id(&self) -> Option<&str> {
+        self.xml_id.as_deref()
+    }
+
+    /// Set the `xml:id` of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_xml_id("entry-1".to_string());
+    /// ```
+    pub fn set_xml_id<V>(&mut self, xml_id: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.xml_id = xml_id.into()
+    }
+
+    /// Return the base URL of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_base("http://example.com/2024/".to_string());
+    /// assert_eq!(entry.base(), Some("http://example.com/2024/"));
+    /// ```
+    pub fn base(&self) -> Option<&str> {
+        self.base.as_deref()
+    }
+
+    /// Set the base URL of this entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Entry;
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_base("http://example.com/2024/".to_string());
+    /// ```
+    pub fn set_base<V>(&mut self, base: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.base = base.into()
+    }
+
+    /// Records the base URL in scope of the text constructs, content and links of this entry,
+    /// given the base URL `parent` in scope of the entry itself.
+    #[cfg(feature = "std")]
+    pub(crate) fn apply_base(&mut self, parent: Option<&str>) {
+        let base = join_base(parent, self.base.as_deref());
+        self.title.apply_base(base.as_deref());
+        for text in self.rights.iter_mut().chain(self.summary.iter_mut()) {
+            text.apply_base(base.as_deref());
+        }
+        if let Some(ref mut content) = self.content {
+            content.apply_base(base.as_deref());
+        }
+        for link in &mut self.links {
+            link.apply_base(base.as_deref());
+        }
+        if let Some(ref mut source) = self.source {
+            source.apply_base(base.as_deref());
+        }
+    }
 }
 
-impl FromXml for Entry {
-    fn from_xml<B: BufRead>(reader: &mut Reader<B>, _: Attributes<'_>) -> Result<Self, Error> {
+/// The body of an entry chosen for display by [`Entry::display_body`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DisplayBody {
+    /// HTML markup.
+    Html(String),
+    /// Plain text.
+    Text(String),
+    /// The URI of content which is not included in the feed.
+    ExternalLink(String),
+}
+
+/// The children of the element which may only appear once.
+#[cfg(feature = "std")]
+const ENTRY_SINGLETONS: &[&str] = &[
+    "title",
+    "id",
+    "updated",
+    "published",
+    "rights",
+    "source",
+    "summary",
+    "content",
+];
+
+#[cfg(feature = "std")]
+impl Entry {
+    /// Creates an entry holding only the attributes of its `<entry>` element.
+    fn from_attributes<B: BufRead>(
+        reader: &Reader<B>,
+        mut atts: Attributes<'_>,
+        opts: &ParseOpts,
+    ) -> Result<Self, Error> {
         let mut entry = Entry::default();
+
+        for att in atts.with_checks(false).flatten() {
+            match decode(att.key.as_ref(), reader)? {
+                Cow::Borrowed("xml:id") => {
+                    entry.xml_id = Some(attr_value(&att, reader, opts)?.into_owned())
+                }
+                Cow::Borrowed("xml:base") => {
+                    entry.base = Some(attr_value(&att, reader, opts)?.into_owned())
+                }
+                _ => {}
+            }
+        }
+
+        Ok(entry)
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromXml for Entry {
+    fn from_xml<B: BufRead>(
+        reader: &mut Reader<B>,
+        atts: Attributes<'_>,
+        opts: &ParseOpts,
+    ) -> Result<Self, Error> {
+        let mut entry = Entry::from_attributes(reader, atts, opts)?;
+        let mut updated = None;
+        let mut seen = Vec::new();
         let mut buf = Vec::new();
 
         loop {
-            match reader.read_event_into(&mut buf).map_err(XmlError::new)? {
-                Event::Start(element) => match decode(element.name().as_ref(), reader)? {
-                    Cow::Borrowed("id") => entry.id = atom_text(reader)?.unwrap_or_default(),
+            let event = reader.read_event_into(&mut buf).map_err(XmlError::new)?;
+            let empty = matches!(event, Event::Empty(_));
+            match event {
+                Event::Start(element) | Event::Empty(element) => match check_duplicate(
+                    decode(element.name().as_ref(), reader)?,
+                    ENTRY_SINGLETONS,
+                    &mut seen,
+                    opts,
+                )? {
+                    Cow::Borrowed("id") => {
+                        entry.id = element_text(reader, empty, opts)?.unwrap_or_default()
+                    }
                     Cow::Borrowed("title") => {
-                        entry.title = Text::from_xml(reader, element.attributes())?
+                        entry.title = Text::from_element(reader, &element, empty, opts)?
                     }
                     Cow::Borrowed("updated") => {
-                        entry.updated =
-                            atom_datetime(reader)?.unwrap_or_else(default_fixed_datetime)
+                        updated = element_datetime(reader, empty, "updated", opts)?
                     }
                     Cow::Borrowed("author") => entry
                         .authors
-                        .push(Person::from_xml(reader, element.attributes())?),
+                        .push(Person::from_element(reader, &element, empty, opts)?),
                     Cow::Borrowed("category") => {
-                        entry.categories.push(Category::from_xml(reader, &element)?);
-                        skip(element.name(), reader)?;
+                        entry
+                            .categories
+                            .push(Category::from_xml(reader, &element, opts)?);
+                        if !empty {
+                            skip(element.name(), reader)?;
+                        }
                     }
                     Cow::Borrowed("contributor") => entry
                         .contributors
-                        .push(Person::from_xml(reader, element.attributes())?),
+                        .push(Person::from_element(reader, &element, empty, opts)?),
                     Cow::Borrowed("link") => {
-                        entry.links.push(Link::from_xml(reader, &element)?);
-                        skip(element.name(), reader)?;
+                        if let Some(link) = Link::from_xml(reader, &element, opts)? {
+                            entry.links.push(link);
+                        }
+                        if !empty {
+                            skip(element.name(), reader)?;
+                        }
+                    }
+                    Cow::Borrowed("published") => {
+                        entry.published = element_datetime(reader, empty, "published", opts)?
                     }
-                    Cow::Borrowed("published") => entry.published = atom_datetime(reader)?,
                     Cow::Borrowed("rights") => {
-                        entry.rights = Some(Text::from_xml(reader, element.attributes())?)
+                        entry.rights = Some(Text::from_element(reader, &element, empty, opts)?)
                     }
                     Cow::Borrowed("source") => {
-                        entry.source = Some(Source::from_xml(reader, element.attributes())?)
+                        entry.source = Some(Source::from_element(reader, &element, empty, opts)?)
                     }
                     Cow::Borrowed("summary") => {
-                        entry.summary = Some(Text::from_xml(reader, element.attributes())?)
+                        entry.summary = Some(if empty {
+                            Text::from_empty_xml(reader, element.attributes(), opts)?
+                        } else {
+                            Text::from_xml_limited(
+                                reader,
+                                element.attributes(),
+                                opts,
+                                opts.max_content_len,
+                            )?
+                        })
                     }
                     Cow::Borrowed("content") => {
-                        entry.content = Some(Content::from_xml(reader, element.attributes())?)
+                        entry.content = Some(Content::from_element(reader, &element, empty, opts)?)
                     }
                     n => {
                         if let Some((ns, name)) = extension_name(n.as_ref()) {
                             parse_extension(
                                 reader,
-                                element.attributes(),
+                                &element,
+                                empty,
                                 ns,
                                 name,
                                 &mut entry.extensions,
+                                opts,
                             )?;
-                        } else {
+                        } else if !empty {
                             skip(element.name(), reader)?;
                         }
                     }
@@ -572,26 +1266,90 @@ impl FromXml for Entry {
             buf.clear();
         }
 
+        entry.updated = match updated {
+            Some(updated) => updated,
+            None => opts.missing_updated.resolve()?,
+        };
+        opts.missing_id.resolve(&mut entry)?;
+        entry.apply_base(None);
+
+        Ok(entry)
+    }
+}
+
+/// Returns the number of leading subtags the `hreflang` of a link has in common with the
+/// requested language tag, and whether the `hreflang` has no further subtags.
+fn matching_subtags(hreflang: &str, lang: &str) -> (usize, bool) {
+    let matching = hreflang
+        .split('-')
+        .zip(lang.split('-'))
+        .take_while(|(a, b)| a.eq_ignore_ascii_case(b))
+        .count();
+    (matching, hreflang.split('-').count() == matching)
+}
+
+#[cfg(feature = "std")]
+impl FromEmptyXml for Entry {
+    fn from_empty_xml<B: BufRead>(
+        reader: &mut Reader<B>,
+        atts: Attributes<'_>,
+        opts: &ParseOpts,
+    ) -> Result<Self, Error> {
+        let mut entry = Entry::from_attributes(reader, atts, opts)?;
+        entry.updated = opts.missing_updated.resolve()?;
+        opts.missing_id.resolve(&mut entry)?;
         Ok(entry)
     }
 }
 
+#[cfg(feature = "std")]
 impl ToXml for Entry {
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        self.to_xml_with_config(writer, &WriteConfig::default())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Entry {
+    /// Writes this entry according to the options of `config`.
+    pub(crate) fn to_xml_with_config<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        config: &WriteConfig,
+    ) -> Result<(), XmlError> {
         let name = "entry";
+        let mut element = BytesStart::new(name);
+        if let Some(ref base) = self.base {
+            element.push_attribute(("xml:base", base.as_str()));
+        }
+        if let Some(ref xml_id) = self.xml_id {
+            element.push_attribute(("xml:id", xml_id.as_str()));
+        }
         writer
-            .write_event(Event::Start(BytesStart::new(name)))
+            .write_event(Event::Start(element))
             .map_err(XmlError::new)?;
-        writer.write_object_named(&self.title, "title")?;
-        writer.write_text_element("id", &self.id)?;
-        writer.write_text_element("updated", &self.updated.to_rfc3339())?;
+        if !config.rfc_element_order {
+            writer.write_object_named(&self.title, "title")?;
+            writer.write_text_element("id", &self.id)?;
+            writer.write_text_element("updated", &format_datetime(&self.updated, config))?;
+        }
         writer.write_objects_named(&self.authors, "author")?;
         writer.write_objects(&self.categories)?;
+        if config.rfc_element_order {
+            if let Some(ref content) = self.content {
+                writer.write_object(content)?;
+            }
+        }
         writer.write_objects_named(&self.contributors, "contributor")?;
-        writer.write_objects(&self.links)?;
+        if config.rfc_element_order {
+            writer.write_text_element("id", &self.id)?;
+        }
+        for link in &self.links {
+            link.to_xml_with_config(writer, config)?;
+        }
 
         if let Some(ref published) = self.published {
-            writer.write_text_element("published", &published.to_rfc3339())?;
+            writer.write_text_element("published", &format_datetime(published, config))?;
         }
 
         if let Some(ref rights) = self.rights {
@@ -599,14 +1357,17 @@ impl ToXml for Entry {
         }
 
         if let Some(ref source) = self.source {
-            writer.write_object(source)?;
+            source.to_xml_with_config(writer, config)?;
         }
 
         if let Some(ref summary) = self.summary {
             writer.write_object_named(summary, "summary")?;
         }
 
-        if let Some(ref content) = self.content {
+        if config.rfc_element_order {
+            writer.write_object_named(&self.title, "title")?;
+            writer.write_text_element("updated", &format_datetime(&self.updated, config))?;
+        } else if let Some(ref content) = self.content {
             writer.write_object(content)?;
         }
 
@@ -640,6 +1401,8 @@ impl Default for Entry {
             summary: None,
             content: None,
             extensions: ExtensionMap::default(),
+            xml_id: None,
+            base: None,
         }
     }
 }
@@ -650,4 +1413,139 @@ impl EntryBuilder {
     pub fn build(&self) -> Entry {
         self.build_impl().unwrap()
     }
+
+    clear_methods! {
+        EntryBuilder => entry {
+            clear_authors: authors, author(Person);
+            clear_categories: categories, category(Category);
+            clear_contributors: contributors, contributor(Person);
+            clear_links: links, link(Link);
+        }
+    }
+
+    /// Adds an extension element with a text value, keeping the extensions set so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::EntryBuilder;
+    ///
+    /// let entry = EntryBuilder::default()
+    ///     .simple_extension("dc", "creator", "John Doe")
+    ///     .simple_extension("dc", "creator", "Jane Doe")
+    ///     .build();
+    /// let creators = &entry.extensions()["dc"]["creator"];
+    /// assert_eq!(creators.len(), 2);
+    /// assert_eq!(creators[0].name(), "dc:creator");
+    /// assert_eq!(creators[1].value(), Some("Jane Doe"));
+    /// ```
+    pub fn simple_extension<P, N, V>(&mut self, prefix: P, name: N, value: V) -> &mut Self
+    where
+        P: Into<String>,
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.merge_extensions(simple_extension(prefix.into(), name.into(), value.into()))
+    }
+
+    /// Adds the given extensions to the extensions set so far.
+    ///
+    /// Unlike `extensions`, which replaces the whole map, and `extension`, which replaces all
+    /// elements of a prefix, elements sharing a prefix and local name are appended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::extension::{ExtensionBuilder, ExtensionMap};
+    /// use atom_syndication::EntryBuilder;
+    ///
+    /// let mut extensions = ExtensionMap::new();
+    /// extensions
+    ///     .entry("dc".to_string())
+    ///     .or_default()
+    ///     .insert(
+    ///         "subject".to_string(),
+    ///         vec![ExtensionBuilder::default().name("dc:subject").value("Technology".to_string()).build()],
+    ///     );
+    ///
+    /// let entry = EntryBuilder::default()
+    ///     .simple_extension("dc", "creator", "John Doe")
+    ///     .merge_extensions(extensions)
+    ///     .build();
+    /// assert_eq!(entry.extensions()["dc"].len(), 2);
+    /// ```
+    pub fn merge_extensions(&mut self, extensions: ExtensionMap) -> &mut Self {
+        merge_extensions(
+            self.extensions.get_or_insert_with(Default::default),
+            extensions,
+        );
+        self
+    }
+
+    /// Sets the last time the entry was modified from an RFC 3339 timestamp.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::EntryBuilder;
+    ///
+    /// # fn main() -> Result<(), atom_syndication::Error> {
+    /// let entry = EntryBuilder::default()
+    ///     .updated_rfc3339("2017-06-03T15:15:44-05:00")?
+    ///     .build();
+    /// assert_eq!(entry.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    ///
+    /// assert!(EntryBuilder::default().updated_rfc3339("yesterday").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn updated_rfc3339(&mut self, updated: &str) -> Result<&mut Self, Error> {
+        self.updated = Some(parse_rfc3339("updated", updated)?);
+        Ok(self)
+    }
+
+    /// Sets the time the entry was published from an RFC 3339 timestamp.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::EntryBuilder;
+    ///
+    /// # fn main() -> Result<(), atom_syndication::Error> {
+    /// let entry = EntryBuilder::default()
+    ///     .published_rfc3339("2017-06-03T15:15:44-05:00")?
+    ///     .build();
+    /// assert_eq!(entry.published().unwrap().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    ///
+    /// assert!(EntryBuilder::default().published_rfc3339("yesterday").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn published_rfc3339(&mut self, published: &str) -> Result<&mut Self, Error> {
+        self.published = Some(Some(parse_rfc3339("published", published)?));
+        Ok(self)
+    }
+
+    /// Sets the publication time to the modification time which has been set so far, unless
+    /// the publication time has been set already.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{EntryBuilder, FixedDateTime};
+    /// use std::str::FromStr;
+    ///
+    /// let updated = FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap();
+    /// let entry = EntryBuilder::default()
+    ///     .updated(updated)
+    ///     .published_from_updated()
+    ///     .build();
+    /// assert_eq!(entry.published(), Some(&updated));
+    /// ```
+    pub fn published_from_updated(&mut self) -> &mut Self {
+        if let (None, Some(updated)) = (&self.published, self.updated) {
+            self.published = Some(Some(updated));
+        }
+        self
+    }
 }
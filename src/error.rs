@@ -9,10 +9,29 @@ pub enum Error {
     Xml(XmlError),
     /// Input did not begin with an opening feed tag.
     InvalidStartTag,
+    /// Input is an RSS or RDF document rather than an Atom feed.
+    NotAtom {
+        /// The name of the root element.
+        root: String,
+    },
+    /// The `<feed>` element is not in the Atom namespace.
+    WrongNamespace {
+        /// The default namespace declared on the element, if any.
+        found: Option<String>,
+    },
     /// Unexpected end of input.
     Eof,
     /// The format of the timestamp is wrong.
-    WrongDatetime(String),
+    WrongDatetime {
+        /// The element holding the timestamp, such as `updated`, `published` or
+        /// `source.updated`.
+        field: &'static str,
+        /// The index of the entry holding the timestamp in document order, or `None` if it is
+        /// not part of an entry.
+        entry: Option<usize>,
+        /// The invalid timestamp.
+        value: String,
+    },
     /// The value of an attribute is wrong.
     WrongAttribute {
         /// The name of the attribute.
@@ -20,6 +39,40 @@ pub enum Error {
         /// Invalid value.
         value: String,
     },
+    /// A required element is missing.
+    MissingElement {
+        /// The name of the element.
+        element: &'static str,
+    },
+    /// A required attribute is missing.
+    MissingAttribute {
+        /// The name of the element.
+        element: &'static str,
+        /// The name of the attribute.
+        attribute: &'static str,
+    },
+    /// An element which may only appear once appeared more than once.
+    DuplicateElement {
+        /// The name of the element.
+        element: &'static str,
+    },
+    /// An extension element uses a namespace prefix which the feed does not declare.
+    UndeclaredNamespace {
+        /// The namespace prefix.
+        prefix: String,
+    },
+    /// A field contains text which cannot be written as well-formed XML.
+    InvalidContent {
+        /// The path of the field, such as `entry.title`.
+        field: String,
+    },
+    /// The input exceeds a limit set in `ParseOpts`.
+    LimitExceeded {
+        /// The name of the option setting the limit, such as `max_extension_depth`.
+        option: &'static str,
+        /// The value of the limit.
+        limit: usize,
+    },
 }
 
 impl StdError for Error {
@@ -27,9 +80,17 @@ impl StdError for Error {
         match *self {
             Error::Xml(ref err) => Some(err),
             Error::InvalidStartTag => None,
+            Error::NotAtom { .. } => None,
+            Error::WrongNamespace { .. } => None,
             Error::Eof => None,
-            Error::WrongDatetime(_) => None,
+            Error::WrongDatetime { .. } => None,
             Error::WrongAttribute { .. } => None,
+            Error::MissingElement { .. } => None,
+            Error::MissingAttribute { .. } => None,
+            Error::DuplicateElement { .. } => None,
+            Error::UndeclaredNamespace { .. } => None,
+            Error::InvalidContent { .. } => None,
+            Error::LimitExceeded { .. } => None,
         }
     }
 }
@@ -39,12 +100,37 @@ impl fmt::Display for Error {
         match *self {
             Error::Xml(ref err) => fmt::Display::fmt(err, f),
             Error::InvalidStartTag => write!(f, "input did not begin with an opening feed tag"),
+            Error::NotAtom { ref root } => {
+                write!(f, "input is not an Atom feed but has a <{}> root", root)
+            }
+            Error::WrongNamespace {
+                found: Some(ref found),
+            } => {
+                write!(
+                    f,
+                    "<feed> element is in namespace {} instead of Atom",
+                    found
+                )
+            }
+            Error::WrongNamespace { found: None } => {
+                write!(f, "<feed> element does not declare the Atom namespace")
+            }
             Error::Eof => write!(f, "unexpected end of input"),
-            Error::WrongDatetime(ref datetime) => write!(
-                f,
-                "timestamps must be formatted by RFC3339, rather than {}",
-                datetime
-            ),
+            Error::WrongDatetime {
+                field,
+                entry,
+                ref value,
+            } => {
+                write!(
+                    f,
+                    "timestamps must be formatted by RFC3339, rather than {} in {}",
+                    value, field
+                )?;
+                if let Some(entry) = entry {
+                    write!(f, " of entry {}", entry)?;
+                }
+                Ok(())
+            }
             Error::WrongAttribute {
                 attribute,
                 ref value,
@@ -53,6 +139,40 @@ impl fmt::Display for Error {
                 "Unsupported value of attribute {}: '{}'.",
                 attribute, value
             ),
+            Error::MissingElement { element } => write!(f, "missing <{}> element", element),
+            Error::MissingAttribute { element, attribute } => {
+                write!(
+                    f,
+                    "missing {} attribute of <{}> element",
+                    attribute, element
+                )
+            }
+            Error::DuplicateElement { element } => {
+                write!(f, "<{}> element appears more than once", element)
+            }
+            Error::UndeclaredNamespace { ref prefix } => {
+                write!(f, "namespace prefix {} is not declared", prefix)
+            }
+            Error::InvalidContent { ref field } => {
+                write!(f, "{} cannot be written as well-formed XML", field)
+            }
+            Error::LimitExceeded { option, limit } => {
+                write!(f, "input exceeds the limit of {} set by {}", limit, option)
+            }
+        }
+    }
+}
+
+impl Error {
+    /// Attributes an invalid timestamp to the entry at the given index.
+    pub(crate) fn in_entry(self, index: usize) -> Self {
+        match self {
+            Error::WrongDatetime { field, value, .. } => Error::WrongDatetime {
+                field,
+                entry: Some(index),
+                value,
+            },
+            err => err,
         }
     }
 }
@@ -63,13 +183,20 @@ impl From<XmlError> for Error {
     }
 }
 
+/// An error that occurred while reading or writing XML.
 #[derive(Debug)]
 pub struct XmlError(Box<dyn StdError + Send + Sync>);
 
 impl XmlError {
-    pub(crate) fn new(err: impl StdError + Send + Sync + 'static) -> Self {
+    /// Wraps an error of the underlying XML library.
+    pub fn new(err: impl StdError + Send + Sync + 'static) -> Self {
         Self(Box::new(err))
     }
+
+    /// Returns the wrapped error.
+    pub fn into_inner(self) -> Box<dyn StdError + Send + Sync> {
+        self.0
+    }
 }
 
 impl StdError for XmlError {
@@ -94,4 +221,13 @@ mod test {
         assert_send_sync::<Error>();
         assert_send_sync::<XmlError>();
     }
+
+    #[test]
+    fn xml_error_into_inner() {
+        let err = XmlError::new(quick_xml::Error::Syntax(
+            quick_xml::errors::SyntaxError::UnclosedComment,
+        ));
+        let inner = err.into_inner();
+        assert!(inner.downcast_ref::<quick_xml::Error>().is_some());
+    }
 }
@@ -1,16 +1,30 @@
-use std::collections::BTreeMap;
-use std::io::Write;
-use std::str;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
 
-use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
-use quick_xml::Writer;
+#[cfg(feature = "std")]
+use std::{io::Write, str};
 
-use crate::error::XmlError;
-use crate::toxml::ToXml;
+#[cfg(feature = "std")]
+use quick_xml::{
+    events::{BytesEnd, BytesStart, BytesText, Event},
+    Writer,
+};
 
+#[cfg(feature = "std")]
+use crate::{error::XmlError, toxml::ToXml, util::FixedDateTime};
+
+#[cfg(feature = "std")]
 pub(crate) mod util;
 
 /// A map of extension namespace prefixes to local names to elements.
+///
+/// Extensions are written ordered by namespace prefix and then by local name. Elements sharing
+/// the same prefix and local name are kept and written in document order, so reading a written
+/// feed again yields the same map and writing it produces the same output.
+///
+/// When serialized with `serde`, the map is represented as nested maps keyed by prefix and local
+/// name, while each `Extension` keeps its qualified name.
 pub type ExtensionMap = BTreeMap<String, BTreeMap<String, Vec<Extension>>>;
 
 /// A namespaced extension.
@@ -29,13 +43,27 @@ pub struct Extension {
     /// The qualified name of the extension element.
     pub name: String,
     /// The content of the extension element.
+    ///
+    /// Surrounding whitespace is kept when reading unless `ParseOpts::trim_whitespace` is set.
+    /// Content consisting only of whitespace is read as `None`.
     pub value: Option<String>,
     /// The attributes for the extension element.
     #[cfg_attr(feature = "builders", builder(setter(each = "attr")))]
     pub attrs: BTreeMap<String, String>,
     /// The children of the extension element. A map of local names to child elements.
+    ///
+    /// Children are written ordered by local name and, for repeated names, in document order.
     #[cfg_attr(feature = "builders", builder(setter(each = "child")))]
     pub children: BTreeMap<String, Vec<Extension>>,
+    /// Children of the extension element kept in document order, which are written after
+    /// `children`.
+    ///
+    /// Children are read into this list instead of `children` when
+    /// `ParseOpts::ordered_extension_children` is set, for extensions whose differently named
+    /// children are ordered.
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "builders", builder(setter(each = "ordered_child")))]
+    pub ordered_children: Vec<Extension>,
 }
 
 impl Extension {
@@ -139,6 +167,82 @@ impl Extension {
         self.attrs = attrs.into();
     }
 
+    /// Return the attribute with the given name parsed as a boolean.
+    ///
+    /// The values "true", "yes" and "1" are `true`, the values "false", "no" and "0" are
+    /// `false`, ignoring case and surrounding whitespace. Returns `None` if the attribute is
+    /// missing or has any other value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::extension::Extension;
+    ///
+    /// let mut extension = Extension::default();
+    /// extension.attrs.insert("explicit".to_string(), "Yes".to_string());
+    /// extension.attrs.insert("block".to_string(), "maybe".to_string());
+    /// assert_eq!(extension.attr_bool("explicit"), Some(true));
+    /// assert_eq!(extension.attr_bool("block"), None);
+    /// assert_eq!(extension.attr_bool("missing"), None);
+    /// ```
+    pub fn attr_bool(&self, key: &str) -> Option<bool> {
+        let value = self.attrs.get(key)?.trim();
+        if ["true", "yes", "1"]
+            .iter()
+            .any(|v| value.eq_ignore_ascii_case(v))
+        {
+            Some(true)
+        } else if ["false", "no", "0"]
+            .iter()
+            .any(|v| value.eq_ignore_ascii_case(v))
+        {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Return the attribute with the given name parsed as an integer.
+    ///
+    /// Returns `None` if the attribute is missing or is not an integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::extension::Extension;
+    ///
+    /// let mut extension = Extension::default();
+    /// extension.attrs.insert("count".to_string(), "42".to_string());
+    /// assert_eq!(extension.attr_i64("count"), Some(42));
+    /// ```
+    pub fn attr_i64(&self, key: &str) -> Option<i64> {
+        self.attrs.get(key)?.trim().parse().ok()
+    }
+
+    /// Return the attribute with the given name parsed as a date and time.
+    ///
+    /// The same formats as for the dates of the feed are accepted. Returns `None` if the
+    /// attribute is missing or is not a date.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::extension::Extension;
+    /// use atom_syndication::FixedDateTime;
+    /// use std::str::FromStr;
+    ///
+    /// let mut extension = Extension::default();
+    /// extension.attrs.insert("when".to_string(), "2017-06-03T15:15:44-05:00".to_string());
+    /// assert_eq!(
+    ///     extension.attr_datetime("when"),
+    ///     Some(FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap())
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn attr_datetime(&self, key: &str) -> Option<FixedDateTime> {
+        diligent_date_parser::parse_date(self.attrs.get(key)?.trim())
+    }
+
     /// Return the children of the extension element.
     ///
     /// A map of local names to child elements.
@@ -178,12 +282,45 @@ impl Extension {
     {
         self.children = children.into();
     }
+
+    /// Return the children of the extension element which are kept in document order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::extension::Extension;
+    ///
+    /// let mut extension = Extension::default();
+    /// extension.set_ordered_children(vec![Extension::default()]);
+    /// assert_eq!(extension.ordered_children().len(), 1);
+    /// ```
+    pub fn ordered_children(&self) -> &[Extension] {
+        self.ordered_children.as_slice()
+    }
+
+    /// Set the children of the extension element which are kept in document order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::extension::Extension;
+    ///
+    /// let mut extension = Extension::default();
+    /// extension.set_ordered_children(vec![Extension::default()]);
+    /// ```
+    pub fn set_ordered_children<V>(&mut self, ordered_children: V)
+    where
+        V: Into<Vec<Extension>>,
+    {
+        self.ordered_children = ordered_children.into();
+    }
 }
 
+#[cfg(feature = "std")]
 impl ToXml for Extension {
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
         let mut element = BytesStart::new(&self.name);
-        element.extend_attributes(self.attrs.iter().map(|a| (a.0.as_bytes(), a.1.as_bytes())));
+        element.extend_attributes(self.attrs.iter().map(|(k, v)| (k.as_str(), v.as_str())));
         writer
             .write_event(Event::Start(element))
             .map_err(XmlError::new)?;
@@ -198,6 +335,10 @@ impl ToXml for Extension {
             extension.to_xml(writer)?;
         }
 
+        for extension in &self.ordered_children {
+            extension.to_xml(writer)?;
+        }
+
         writer
             .write_event(Event::End(BytesEnd::new(&self.name)))
             .map_err(XmlError::new)?;
@@ -211,4 +352,55 @@ impl ExtensionBuilder {
     pub fn build(&self) -> Extension {
         self.build_impl().unwrap()
     }
+
+    /// Removes the attributes set so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::extension::ExtensionBuilder;
+    ///
+    /// let mut builder = ExtensionBuilder::default();
+    /// builder.attr(("key".to_string(), "value".to_string()));
+    /// let extension = builder.clear_attrs().build();
+    /// assert!(extension.attrs().is_empty());
+    /// ```
+    pub fn clear_attrs(&mut self) -> &mut Self {
+        self.attrs = Some(BTreeMap::new());
+        self
+    }
+
+    /// Removes the children set so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::extension::ExtensionBuilder;
+    ///
+    /// let mut builder = ExtensionBuilder::default();
+    /// builder.child(("child".to_string(), Vec::new()));
+    /// let extension = builder.clear_children().build();
+    /// assert!(extension.children().is_empty());
+    /// ```
+    pub fn clear_children(&mut self) -> &mut Self {
+        self.children = Some(BTreeMap::new());
+        self
+    }
+
+    /// Removes the ordered children set so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::extension::{Extension, ExtensionBuilder};
+    ///
+    /// let mut builder = ExtensionBuilder::default();
+    /// builder.ordered_child(Extension::default());
+    /// let extension = builder.clear_ordered_children().build();
+    /// assert!(extension.ordered_children().is_empty());
+    /// ```
+    pub fn clear_ordered_children(&mut self) -> &mut Self {
+        self.ordered_children = Some(Vec::new());
+        self
+    }
 }
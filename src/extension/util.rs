@@ -1,32 +1,100 @@
 use std::collections::BTreeMap;
 use std::io::BufRead;
 
-use quick_xml::events::attributes::Attributes;
-use quick_xml::events::Event;
+use quick_xml::events::{BytesStart, Event};
 use quick_xml::Reader;
 
 use crate::error::{Error, XmlError};
 use crate::extension::{Extension, ExtensionMap};
-use crate::util::{attr_value, decode};
-
+use crate::feed::ParseOpts;
+use crate::util::{attr_value, decode, resolve_entity};
+
+/// Splits a qualified element name into its prefix and local name.
+///
+/// Returns `None`, so that the element is skipped, unless the name has exactly one colon with a
+/// non-empty prefix and local name on either side. Names like `:foo`, `a:` and `a:b:c` are not
+/// valid qualified names and are never stored as extensions.
 pub fn extension_name(element_name: &str) -> Option<(&str, &str)> {
-    let mut split = element_name.splitn(2, ':');
+    let mut split = element_name.split(':');
     let ns = split.next().filter(|ns| !ns.is_empty())?;
-    let name = split.next()?;
+    let name = split.next().filter(|name| !name.is_empty())?;
+    if split.next().is_some() {
+        return None;
+    }
     Some((ns, name))
 }
 
+/// Appends the extensions of `source` to those of `target` with the same prefix and local name.
+#[cfg(feature = "builders")]
+pub(crate) fn merge_extensions(target: &mut ExtensionMap, source: ExtensionMap) {
+    for (ns, map) in source {
+        let target = target.entry(ns).or_default();
+        for (name, items) in map {
+            target.entry(name).or_default().extend(items);
+        }
+    }
+}
+
+/// Returns the first namespace prefix of the extensions, or of their children, which is not
+/// declared in `namespaces`.
+#[cfg(feature = "builders")]
+pub(crate) fn undeclared_prefix<'a>(
+    extensions: &'a ExtensionMap,
+    namespaces: &BTreeMap<String, String>,
+) -> Option<&'a str> {
+    extensions
+        .values()
+        .flat_map(|map| map.values())
+        .flatten()
+        .find_map(|extension| extension_undeclared_prefix(extension, namespaces))
+}
+
+#[cfg(feature = "builders")]
+fn extension_undeclared_prefix<'a>(
+    extension: &'a Extension,
+    namespaces: &BTreeMap<String, String>,
+) -> Option<&'a str> {
+    if let Some((prefix, _)) = extension.name.split_once(':') {
+        if prefix != "xml" && !namespaces.contains_key(prefix) {
+            return Some(prefix);
+        }
+    }
+    extension
+        .children
+        .values()
+        .flatten()
+        .chain(&extension.ordered_children)
+        .find_map(|child| extension_undeclared_prefix(child, namespaces))
+}
+
+/// Creates a map holding a single extension element with a text value.
+#[cfg(feature = "builders")]
+pub(crate) fn simple_extension(prefix: String, name: String, value: String) -> ExtensionMap {
+    let extension = Extension {
+        name: format!("{}:{}", prefix, name),
+        value: Some(value),
+        ..Default::default()
+    };
+    let mut map = BTreeMap::new();
+    map.insert(name, vec![extension]);
+    let mut extensions = ExtensionMap::new();
+    extensions.insert(prefix, map);
+    extensions
+}
+
 pub fn parse_extension<R>(
     reader: &mut Reader<R>,
-    atts: Attributes<'_>,
+    element: &BytesStart<'_>,
+    empty: bool,
     ns: &str,
     name: &str,
     extensions: &mut ExtensionMap,
+    opts: &ParseOpts,
 ) -> Result<(), Error>
 where
     R: BufRead,
 {
-    let ext = parse_extension_element(reader, atts)?;
+    let ext = parse_extension_element(reader, element, empty, 1, opts)?;
 
     if !extensions.contains_key(ns) {
         extensions.insert(ns.to_string(), BTreeMap::new());
@@ -51,57 +119,98 @@ where
     Ok(())
 }
 
+/// Reads an extension from `element`, which has no content if `empty` is set and is nested
+/// `depth` levels deep.
 fn parse_extension_element<R: BufRead>(
     reader: &mut Reader<R>,
-    mut atts: Attributes<'_>,
+    element: &BytesStart<'_>,
+    empty: bool,
+    depth: usize,
+    opts: &ParseOpts,
 ) -> Result<Extension, Error> {
-    let mut extension = Extension::default();
+    if let Some(limit) = opts.max_extension_depth {
+        if depth > limit {
+            return Err(Error::LimitExceeded {
+                option: "max_extension_depth",
+                limit,
+            });
+        }
+    }
+    let mut extension = Extension {
+        name: decode(element.name().as_ref(), reader)?.into(),
+        ..Default::default()
+    };
     let mut buf = Vec::new();
 
-    for attr in atts.with_checks(false).flatten() {
+    for attr in element.attributes().with_checks(false).flatten() {
         let key = decode(attr.key.local_name().as_ref(), reader)?.to_string();
-        let value = attr_value(&attr, reader)?.to_string();
+        let value = attr_value(&attr, reader, opts)?.into_owned();
         extension.attrs.insert(key, value);
     }
 
+    if empty {
+        return Ok(extension);
+    }
+
     let mut text = String::new();
     loop {
-        match reader.read_event_into(&mut buf).map_err(XmlError::new)? {
-            Event::Start(element) => {
-                let ext = parse_extension_element(reader, element.attributes())?;
-                let element_local_name = element.local_name();
-                let name = decode(element_local_name.as_ref(), reader)?;
-
-                if !extension.children.contains_key(&*name) {
-                    extension.children.insert(name.to_string(), Vec::new());
+        let event = reader.read_event_into(&mut buf).map_err(XmlError::new)?;
+        let empty = matches!(event, Event::Empty(_));
+        match event {
+            Event::Start(element) | Event::Empty(element) => {
+                let ext = parse_extension_element(reader, &element, empty, depth + 1, opts)?;
+                if opts.ordered_extension_children {
+                    extension.ordered_children.push(ext);
+                } else {
+                    let element_local_name = element.local_name();
+                    let name = decode(element_local_name.as_ref(), reader)?;
+
+                    if !extension.children.contains_key(&*name) {
+                        extension.children.insert(name.to_string(), Vec::new());
+                    }
+
+                    let items = match extension.children.get_mut(&*name) {
+                        Some(items) => items,
+                        None => unreachable!(),
+                    };
+
+                    items.push(ext);
                 }
-
-                let items = match extension.children.get_mut(&*name) {
-                    Some(items) => items,
-                    None => unreachable!(),
-                };
-
-                items.push(ext);
             }
             Event::CData(element) => {
                 text.push_str(decode(&element, reader)?.as_ref());
             }
             Event::Text(element) => {
-                text.push_str(element.unescape().map_err(XmlError::new)?.as_ref());
-            }
-            Event::End(element) => {
-                extension.name = decode(element.name().as_ref(), reader)?.into();
-                break;
+                text.push_str(
+                    element
+                        .unescape_with(|entity| resolve_entity(entity, opts))
+                        .map_err(XmlError::new)?
+                        .as_ref(),
+                );
             }
+            Event::End(_) => break,
             Event::Eof => return Err(Error::Eof),
             _ => {}
         }
 
         buf.clear();
     }
-    extension.value = Some(text.trim())
-        .filter(|t| !t.is_empty())
-        .map(ToString::to_string);
+    extension.value = Some(text).filter(|text| !text.trim().is_empty());
 
     Ok(extension)
 }
+
+#[cfg(test)]
+mod test {
+    use super::extension_name;
+
+    #[test]
+    fn test_extension_name() {
+        assert_eq!(extension_name("a:b"), Some(("a", "b")));
+        assert_eq!(extension_name("foo"), None);
+        assert_eq!(extension_name(":foo"), None);
+        assert_eq!(extension_name("a:"), None);
+        assert_eq!(extension_name(":"), None);
+        assert_eq!(extension_name("a:b:c"), None);
+    }
+}
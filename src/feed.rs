@@ -1,27 +1,72 @@
-use std::borrow::Cow;
-use std::collections::BTreeMap;
-use std::io::{BufRead, Write};
-use std::str::{self, FromStr};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+
+#[cfg(feature = "std")]
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt,
+    io::{BufRead, BufWriter, Write},
+    str::{self, FromStr},
+};
 
-use quick_xml::events::attributes::Attributes;
-use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
-use quick_xml::{Reader, Writer};
+#[cfg(feature = "gzip")]
+use flate2::bufread::GzDecoder;
+#[cfg(feature = "std")]
+use quick_xml::{
+    events::{attributes::Attributes, BytesDecl, BytesEnd, BytesPI, BytesStart, BytesText, Event},
+    Reader, Writer,
+};
 
 use crate::category::Category;
 use crate::entry::Entry;
-use crate::error::{Error, XmlError};
-use crate::extension::util::{extension_name, parse_extension};
+#[cfg(all(feature = "builders", feature = "std"))]
+use crate::extension::util::undeclared_prefix;
+#[cfg(feature = "builders")]
+use crate::extension::util::{merge_extensions, simple_extension};
 use crate::extension::ExtensionMap;
-use crate::fromxml::FromXml;
 use crate::generator::Generator;
-use crate::link::Link;
+use crate::link::{Link, LinkRel};
 use crate::person::Person;
 use crate::text::Text;
-use crate::toxml::{ToXml, WriterExt};
-use crate::util::{
-    atom_datetime, atom_text, attr_value, decode, default_fixed_datetime, skip, FixedDateTime,
+#[cfg(feature = "builders")]
+use crate::util::parse_rfc3339;
+use crate::util::{default_fixed_datetime, join_base, FixedDateTime};
+#[cfg(feature = "std")]
+use crate::{
+    check::CheckXml,
+    error::{Error, XmlError},
+    extension::util::{extension_name, parse_extension},
+    fromxml::{FromEmptyXml, FromXml},
+    toxml::{CountingWriter, CrLfWriter, FmtWriter, ToXml, WriterExt},
+    util::{
+        attr_value, check_duplicate, decode, element_datetime, element_text, format_datetime, now,
+        skip,
+    },
+    ATOM_NS,
 };
 
+#[cfg(feature = "std")]
+/// Newline sequence used when writing XML
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineEnding {
+    /// Unix style `\n`
+    Lf,
+    /// Windows style `\r\n`
+    CrLf,
+}
+
+#[cfg(feature = "std")]
+#[allow(clippy::derivable_impls)]
+impl Default for LineEnding {
+    fn default() -> Self {
+        LineEnding::Lf
+    }
+}
+
+#[cfg(feature = "std")]
 /// Various options which control XML writer
 #[derive(Clone, Copy)]
 pub struct WriteConfig {
@@ -29,17 +74,261 @@ pub struct WriteConfig {
     pub write_document_declaration: bool,
     /// Indent XML tags. Default is `None`.
     pub indent_size: Option<usize>,
+    /// Newline sequence written after the declaration and between indented tags. Line breaks
+    /// inside of text values are written as they are. Default is `LineEnding::Lf`.
+    pub line_ending: LineEnding,
+    /// Write a `<generator>` identifying this crate if the feed has no generator. Default is
+    /// `false`.
+    pub write_default_generator: bool,
+    /// Leave out the `rel` attribute of links whose relation is `alternate`, which is the
+    /// relation assumed when reading a link without one. Default is `false`.
+    pub omit_alternate_rel: bool,
+    /// Write dates with the fractional seconds they were read or set with.
+    ///
+    /// Otherwise dates are written in whole seconds with a numeric offset, such as
+    /// `2017-06-03T15:15:44+00:00`, so that all dates of a feed have the same precision.
+    /// Default is `false`.
+    pub preserve_subseconds: bool,
+    /// Write the elements of feeds, entries and sources in the order of the schema in RFC 4287,
+    /// which lists them alphabetically, for validators which expect that order.
+    ///
+    /// That is authors, categories, content, contributors, generator, icon, id, links, logo,
+    /// published, rights, source, subtitle, summary, title, updated and extension elements,
+    /// followed by the entries of a feed. Otherwise the title, id and updated date come first,
+    /// followed by the other elements in that order, with the content of an entry after its
+    /// summary. In both cases the extension elements of a feed come before its entries. RFC 4287
+    /// allows any order. Default is `false`.
+    pub rfc_element_order: bool,
 }
 
+#[cfg(feature = "std")]
 impl Default for WriteConfig {
     fn default() -> Self {
         Self {
             write_document_declaration: true,
             indent_size: None,
+            line_ending: LineEnding::default(),
+            write_default_generator: false,
+            omit_alternate_rel: false,
+            preserve_subseconds: false,
+            rfc_element_order: false,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+/// How a missing or empty `<updated>` element is read
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MissingDate {
+    /// Use the Unix epoch, `1970-01-01T00:00:00Z`
+    Epoch,
+    /// Fail with `Error::MissingElement`
+    Error,
+    /// Use the current time
+    Now,
+}
+
+#[cfg(feature = "std")]
+#[allow(clippy::derivable_impls)]
+impl Default for MissingDate {
+    fn default() -> Self {
+        MissingDate::Epoch
+    }
+}
+
+#[cfg(feature = "std")]
+impl MissingDate {
+    pub(crate) fn resolve(self) -> Result<FixedDateTime, Error> {
+        match self {
+            MissingDate::Epoch => Ok(default_fixed_datetime()),
+            MissingDate::Error => Err(Error::MissingElement { element: "updated" }),
+            MissingDate::Now => Ok(now()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+/// How a `<link>` element without an `href` attribute is read
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MissingHref {
+    /// Keep the link with an empty `href`
+    Empty,
+    /// Leave out the link
+    Skip,
+    /// Fail with `Error::MissingAttribute`
+    Error,
+}
+
+#[cfg(feature = "std")]
+#[allow(clippy::derivable_impls)]
+impl Default for MissingHref {
+    fn default() -> Self {
+        MissingHref::Empty
+    }
+}
+
+#[cfg(feature = "std")]
+/// How an entry without an `<id>` element is read
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MissingId {
+    /// Keep the entry with an empty ID
+    Empty,
+    /// Set an ID as done by `Entry::ensure_id`
+    Synthesize,
+    /// Fail with `Error::MissingElement`
+    Error,
+}
+
+#[cfg(feature = "std")]
+#[allow(clippy::derivable_impls)]
+impl Default for MissingId {
+    fn default() -> Self {
+        MissingId::Empty
+    }
+}
+
+#[cfg(feature = "std")]
+impl MissingId {
+    pub(crate) fn resolve(self, entry: &mut Entry) -> Result<(), Error> {
+        if !entry.id.trim().is_empty() {
+            return Ok(());
+        }
+        match self {
+            MissingId::Empty => {}
+            MissingId::Synthesize => {
+                entry.ensure_id();
+            }
+            MissingId::Error => return Err(Error::MissingElement { element: "id" }),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+/// Various options which control XML reader
+#[derive(Debug, Clone)]
+pub struct ParseOpts {
+    /// Additional named entities (e.g. `nbsp` for `&nbsp;`) with their replacement text.
+    ///
+    /// They are consulted before the predefined XML entities when unescaping text and
+    /// attribute values. Entities found in neither are still reported as an error.
+    /// Default is empty.
+    pub entities: HashMap<String, String>,
+    /// Resolve named HTML5 entities (e.g. `&nbsp;`, `&copy;`) which are not defined by XML.
+    ///
+    /// Custom `entities` still take precedence. This option is ignored unless the
+    /// `html-entities` feature is enabled. Default is `false`.
+    pub html_entities: bool,
+    /// Treat `<content>` without a markup type as xhtml if its body is a single wrapping element.
+    ///
+    /// Applies when `type` is absent or `"text"` and there is no `src`. Such content gets its
+    /// `content_type` set to `"xhtml"`. Default is `false`.
+    pub sniff_xhtml_content: bool,
+    /// Read `<content type="html">` which contains child elements instead of escaped markup,
+    /// like `<content type="html"><p>a &amp; b</p></content>`, the way xhtml content is read.
+    ///
+    /// The value is then `<p>a &amp; b</p>`, which keeps the markup well-formed, instead of
+    /// `<p>a & b</p>`. The `content_type` stays `"html"`. Content without child elements is not
+    /// affected. Default is `false`.
+    pub html_elements_as_xhtml: bool,
+    /// Keep comments and processing instructions found before the `<feed>` element in
+    /// `Feed::prolog` and comments found directly within it in `Feed::comments`.
+    ///
+    /// Default is `false`.
+    pub preserve_comments: bool,
+    /// Read text constructs with an unknown `type` attribute as plain text instead of failing
+    /// with `Error::WrongAttribute`.
+    ///
+    /// Default is `false`.
+    pub lenient_text_type: bool,
+    /// Trim leading and trailing whitespace of every text node, as done by the `trim_text`
+    /// option of the XML reader.
+    ///
+    /// Otherwise text is kept as it is, including leading and trailing whitespace of text
+    /// constructs, content and extension elements. The only exception are extension elements
+    /// with whitespace-only text, such as ones which just contain other elements, which get no
+    /// value. When enabled, `<title> Title </title>` is read as `"Title"` and whitespace
+    /// between inline elements of markup is removed. Default is `false`.
+    pub trim_whitespace: bool,
+    /// How a missing or empty `<updated>` element of a feed, entry or source is read.
+    ///
+    /// Default is `MissingDate::Epoch`.
+    pub missing_updated: MissingDate,
+    /// Fail with `Error::DuplicateElement` if an element which may only appear once in a feed
+    /// or entry, such as `<id>` or `<updated>`, appears more than once.
+    ///
+    /// Otherwise the last one is used. Default is `false`.
+    pub deny_duplicate_elements: bool,
+    /// How a `<link>` element without an `href` attribute, which is required, is read.
+    ///
+    /// A link with an empty `href=""` is not affected, since an empty URI reference is valid.
+    /// Default is `MissingHref::Empty`, which keeps the link so that it is written back as
+    /// `<link href=""/>`.
+    pub missing_href: MissingHref,
+    /// How a missing or empty `<id>` element of an entry is read.
+    ///
+    /// Default is `MissingId::Empty`. Note that entries with an empty ID all share the same
+    /// `Entry::dedup_key`, so aggregators which key entries on their ID should use
+    /// `MissingId::Synthesize`.
+    pub missing_id: MissingId,
+    /// Fail with `Error::WrongNamespace` unless the default namespace declared on the
+    /// `<feed>` element is `http://www.w3.org/2005/Atom`.
+    ///
+    /// Default is `false`, which accepts feeds with a missing or mistyped `xmlns`.
+    pub require_atom_namespace: bool,
+    /// Read the children of extension elements into `Extension::ordered_children`, which keeps
+    /// them in document order, instead of `Extension::children`, which groups them by name.
+    ///
+    /// Default is `false`.
+    pub ordered_extension_children: bool,
+    /// The maximum length in bytes of the value read for the `<content>` and `<summary>` of an
+    /// entry.
+    ///
+    /// Longer values are cut off at a character boundary, and `Content::truncated` or
+    /// `Text::truncated` is set. The rest of the element is skipped without being buffered.
+    /// Note that truncated markup may be unbalanced. Default is `None`.
+    pub max_content_len: Option<usize>,
+    /// The maximum depth of nested extension elements, counting an extension element directly
+    /// within a feed or entry as 1.
+    ///
+    /// Reading fails with `Error::LimitExceeded` if extension elements are nested any deeper,
+    /// which bounds the stack used for adversarial input. Default is `Some(64)`.
+    pub max_extension_depth: Option<usize>,
+}
+
+#[cfg(feature = "std")]
+impl Default for ParseOpts {
+    fn default() -> Self {
+        ParseOpts {
+            entities: HashMap::new(),
+            html_entities: false,
+            sniff_xhtml_content: false,
+            html_elements_as_xhtml: false,
+            preserve_comments: false,
+            lenient_text_type: false,
+            trim_whitespace: false,
+            missing_updated: MissingDate::default(),
+            deny_duplicate_elements: false,
+            missing_href: MissingHref::default(),
+            missing_id: MissingId::default(),
+            require_atom_namespace: false,
+            ordered_extension_children: false,
+            max_content_len: None,
+            max_extension_depth: Some(64),
         }
     }
 }
 
+/// A comment or processing instruction preceding the feed element
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PrologItem {
+    /// A comment with the text between `<!--` and `-->`
+    Comment(String),
+    /// A processing instruction with the target and content between `<?` and `?>`
+    ProcessingInstruction(String),
+}
+
 /// Represents an Atom feed
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[derive(Debug, Clone, PartialEq)]
@@ -54,6 +343,8 @@ impl Default for WriteConfig {
 )]
 pub struct Feed {
     /// A human-readable title for the feed.
+    ///
+    /// The title is required, so it is always written, even when it is empty.
     pub title: Text,
     /// A universally unique and permanent URI.
     pub id: String,
@@ -94,9 +385,54 @@ pub struct Feed {
     pub base: Option<String>,
     /// Indicates the natural language for the element.
     pub lang: Option<String>,
+    /// The `xml:id` of the element, for references from elsewhere in the document.
+    pub xml_id: Option<String>,
+    /// Comments and processing instructions preceding the feed element.
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "builders", builder(setter(each = "prolog_item")))]
+    pub prolog: Vec<PrologItem>,
+    /// Comments found directly within the feed element.
+    #[cfg_attr(feature = "serde", serde(default))]
+    #[cfg_attr(feature = "builders", builder(setter(each = "comment")))]
+    pub comments: Vec<String>,
 }
 
 impl Feed {
+    /// Creates a feed with the elements required by RFC 4287: a unique id, a title and the
+    /// last time it was modified.
+    ///
+    /// `Default` remains available for building a feed step by step, but leaves the id empty
+    /// and the date at the Unix epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, FixedDateTime};
+    /// use std::str::FromStr;
+    ///
+    /// let feed = Feed::new(
+    ///     "urn:uuid:60a76c80-d399-11d9-b93c-0003939e0af6",
+    ///     "Feed Title",
+    ///     FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap(),
+    /// );
+    /// assert_eq!(feed.id(), "urn:uuid:60a76c80-d399-11d9-b93c-0003939e0af6");
+    /// assert_eq!(feed.title(), "Feed Title");
+    /// assert_eq!(feed.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    /// ```
+    pub fn new<I, T, U>(id: I, title: T, updated: U) -> Self
+    where
+        I: Into<String>,
+        T: Into<Text>,
+        U: Into<FixedDateTime>,
+    {
+        Feed {
+            id: id.into(),
+            title: title.into(),
+            updated: updated.into(),
+            ..Default::default()
+        }
+    }
+
     /// Attempt to read an Atom feed from the reader.
     ///
     /// # Examples
@@ -109,20 +445,268 @@ impl Feed {
     /// let file = File::open("example.xml").unwrap();
     /// let feed = Feed::read_from(BufReader::new(file)).unwrap();
     /// ```
+    #[cfg(feature = "std")]
     pub fn read_from<B: BufRead>(reader: B) -> Result<Feed, Error> {
+        Feed::read_from_opts(reader, &ParseOpts::default())
+    }
+
+    /// Attempt to read an Atom feed from the reader, resolving the given custom entities.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use atom_syndication::Feed;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut entities = HashMap::new();
+    /// entities.insert("nbsp".to_string(), "\u{a0}".to_string());
+    ///
+    /// let xml = "<feed><title>Feed&nbsp;Title</title></feed>";
+    /// let feed = Feed::read_from_with_entities(xml.as_bytes(), &entities)?;
+    /// assert_eq!(feed.title(), "Feed\u{a0}Title");
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn read_from_with_entities<B: BufRead>(
+        reader: B,
+        entities: &HashMap<String, String>,
+    ) -> Result<Feed, Error> {
+        let opts = ParseOpts {
+            entities: entities.clone(),
+            ..ParseOpts::default()
+        };
+        Feed::read_from_opts(reader, &opts)
+    }
+
+    /// Attempt to read an Atom feed from the reader using the given `ParseOpts`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io::BufReader;
+    /// use std::fs::File;
+    /// use atom_syndication::{Feed, ParseOpts};
+    ///
+    /// let file = File::open("example.xml").unwrap();
+    /// let feed = Feed::read_from_opts(BufReader::new(file), &ParseOpts::default()).unwrap();
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn read_from_opts<B: BufRead>(mut reader: B, opts: &ParseOpts) -> Result<Feed, Error> {
+        Feed::read_from_dyn_opts(&mut reader, opts)
+    }
+
+    /// Attempt to read an Atom feed from a reader trait object.
+    ///
+    /// The generic reading methods delegate to this one, so the parser is only compiled once
+    /// regardless of how many reader types are used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::BufRead;
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut reader: Box<dyn BufRead> = Box::new("<feed></feed>".as_bytes());
+    /// let feed = Feed::read_from_dyn(&mut reader).unwrap();
+    /// assert!(!feed.has_entries());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn read_from_dyn(reader: &mut dyn BufRead) -> Result<Feed, Error> {
+        Feed::read_from_dyn_opts(reader, &ParseOpts::default())
+    }
+
+    #[cfg(feature = "std")]
+    fn read_from_dyn_opts(reader: &mut dyn BufRead, opts: &ParseOpts) -> Result<Feed, Error> {
+        Feed::read_from_dyn_filtered(reader, opts, &mut |_| true)
+    }
+
+    /// Attempt to read an Atom feed from the reader, keeping only the entries for which the
+    /// predicate returns `true`.
+    ///
+    /// Each entry is passed to the predicate as soon as it has been read and is dropped right
+    /// away if rejected, so memory use is bounded by the accepted entries. This is useful to
+    /// skip entries which have been seen before.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use atom_syndication::{Feed, FixedDateTime};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let xml = r#"<feed>
+    ///     <entry><id>urn:new</id><updated>2024-02-01T00:00:00Z</updated></entry>
+    ///     <entry><id>urn:old</id><updated>2023-12-01T00:00:00Z</updated></entry>
+    /// </feed>"#;
+    /// let last_poll = FixedDateTime::from_str("2024-01-01T00:00:00Z").unwrap();
+    /// let feed = Feed::read_from_filtered(xml.as_bytes(), |entry| *entry.updated() > last_poll)?;
+    /// assert_eq!(feed.entries().len(), 1);
+    /// assert_eq!(feed.entries()[0].id(), "urn:new");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn read_from_filtered<B, F>(reader: B, predicate: F) -> Result<Feed, Error>
+    where
+        B: BufRead,
+        F: FnMut(&Entry) -> bool,
+    {
+        Feed::read_from_filtered_opts(reader, &ParseOpts::default(), predicate)
+    }
+
+    /// Attempt to read an Atom feed from the reader with the given options, keeping only the
+    /// entries for which the predicate returns `true` like `read_from_filtered`.
+    #[cfg(feature = "std")]
+    pub fn read_from_filtered_opts<B, F>(
+        mut reader: B,
+        opts: &ParseOpts,
+        mut predicate: F,
+    ) -> Result<Feed, Error>
+    where
+        B: BufRead,
+        F: FnMut(&Entry) -> bool,
+    {
+        Feed::read_from_dyn_filtered(&mut reader, opts, &mut predicate)
+    }
+
+    #[cfg(feature = "std")]
+    fn read_from_dyn_filtered(
+        reader: &mut dyn BufRead,
+        opts: &ParseOpts,
+        filter: &mut dyn FnMut(&Entry) -> bool,
+    ) -> Result<Feed, Error> {
         let mut reader = Reader::from_reader(reader);
-        reader.config_mut().expand_empty_elements = true;
+        reader.config_mut().trim_text(opts.trim_whitespace);
 
         let mut buf = Vec::new();
+        let mut prolog = Vec::new();
 
         loop {
-            match reader.read_event_into(&mut buf).map_err(XmlError::new)? {
-                Event::Start(element) => {
-                    if decode(element.name().as_ref(), &reader)? == "feed" {
-                        return Feed::from_xml(&mut reader, element.attributes());
-                    } else {
-                        return Err(Error::InvalidStartTag);
-                    }
+            let event = reader.read_event_into(&mut buf).map_err(XmlError::new)?;
+            let empty = matches!(event, Event::Empty(_));
+            match event {
+                Event::Start(element) | Event::Empty(element) => {
+                    let name = decode(element.name().as_ref(), &reader)?.into_owned();
+                    return match name.as_str() {
+                        "feed" => {
+                            let mut feed = if empty {
+                                Feed::from_empty_xml(&mut reader, element.attributes(), opts)?
+                            } else {
+                                Feed::from_xml_filtered(
+                                    &mut reader,
+                                    element.attributes(),
+                                    opts,
+                                    filter,
+                                )?
+                            };
+                            feed.prolog = prolog;
+                            Ok(feed)
+                        }
+                        "rss" | "rdf:RDF" => Err(Error::NotAtom { root: name }),
+                        _ => Err(Error::InvalidStartTag),
+                    };
+                }
+                Event::Comment(comment) if opts.preserve_comments => {
+                    let comment = decode(&comment, &reader)?.into_owned();
+                    prolog.push(PrologItem::Comment(comment));
+                }
+                Event::PI(pi) if opts.preserve_comments => {
+                    let pi = decode(&pi, &reader)?.into_owned();
+                    prolog.push(PrologItem::ProcessingInstruction(pi));
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+
+            buf.clear();
+        }
+
+        Err(Error::Eof)
+    }
+
+    /// Attempt to read an Atom feed from a gzip-compressed reader, e.g. the body of a response
+    /// sent with `Content-Encoding: gzip`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    /// use std::io::BufReader;
+    /// use atom_syndication::Feed;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = File::open("example.xml.gz")?;
+    /// let feed = Feed::read_gzip(BufReader::new(file))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "gzip")]
+    pub fn read_gzip<R: BufRead>(reader: R) -> Result<Feed, Error> {
+        Feed::read_from(std::io::BufReader::new(GzDecoder::new(reader)))
+    }
+
+    /// Attempt to read the first Atom feed found at any depth of an XML document.
+    ///
+    /// Unlike `read_from`, the `<feed>` element does not need to be the root, so feeds embedded
+    /// in other documents can be extracted. Markup surrounding the feed is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let xml = r#"<envelope><body><feed xmlns="http://www.w3.org/2005/Atom"><title>Feed Title</title></feed></body></envelope>"#;
+    /// let feed = Feed::find_and_read(xml.as_bytes())?;
+    /// assert_eq!(feed.title(), "Feed Title");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_and_read<B: BufRead>(mut reader: B) -> Result<Feed, Error> {
+        Feed::find_and_read_dyn(&mut reader, &ParseOpts::default())
+    }
+
+    /// Attempt to read the first Atom feed found at any depth of an XML document using the
+    /// given `ParseOpts`, like `find_and_read`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, ParseOpts};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let xml = r#"<envelope><feed><title> Feed Title </title></feed></envelope>"#;
+    /// let opts = ParseOpts {
+    ///     trim_whitespace: true,
+    ///     ..Default::default()
+    /// };
+    /// let feed = Feed::find_and_read_opts(xml.as_bytes(), &opts)?;
+    /// assert_eq!(feed.title(), "Feed Title");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_and_read_opts<B: BufRead>(mut reader: B, opts: &ParseOpts) -> Result<Feed, Error> {
+        Feed::find_and_read_dyn(&mut reader, opts)
+    }
+
+    #[cfg(feature = "std")]
+    fn find_and_read_dyn(reader: &mut dyn BufRead, opts: &ParseOpts) -> Result<Feed, Error> {
+        let mut reader = Reader::from_reader(reader);
+        reader.config_mut().trim_text(opts.trim_whitespace);
+
+        let mut buf = Vec::new();
+
+        loop {
+            let event = reader.read_event_into(&mut buf).map_err(XmlError::new)?;
+            let empty = matches!(event, Event::Empty(_));
+            match event {
+                Event::Start(element) | Event::Empty(element)
+                    if decode(element.name().as_ref(), &reader)? == "feed" =>
+                {
+                    return Feed::from_element(&mut reader, &element, empty, opts);
                 }
                 Event::Eof => break,
                 _ => {}
@@ -136,6 +720,9 @@ impl Feed {
 
     /// Attempt to write this Atom feed to a writer using default `WriteConfig`.
     ///
+    /// The output is buffered and flushed to the writer before it is returned, so an unbuffered
+    /// `File` or `TcpStream` does not need to be wrapped in a `BufWriter`.
+    ///
     /// # Examples
     ///
     /// ```
@@ -155,12 +742,90 @@ impl Feed {
     /// <feed xmlns="http://www.w3.org/2005/Atom"><title>Feed Title</title><id>Feed ID</id><updated>1970-01-01T00:00:00+00:00</updated></feed>"#);
     /// # Ok(()) }
     /// ```
+    #[cfg(feature = "std")]
     pub fn write_to<W: Write>(&self, writer: W) -> Result<W, Error> {
-        self.write_with_config(writer, WriteConfig::default())
+        self.write_with_config(BufWriter::new(writer), WriteConfig::default())?
+            .into_inner()
+            .map_err(|err| XmlError::new(err.into_error()).into())
+    }
+
+    /// Write this Atom feed to a `fmt::Write` sink such as a `String` using default
+    /// `WriteConfig`.
+    ///
+    /// The output is the same as that of `to_string`, but is appended to the sink as it is
+    /// written instead of being collected in a separate buffer first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// # fn main() -> std::fmt::Result {
+    /// let feed = Feed::default();
+    /// let mut out = String::from("<!-- cached -->");
+    /// feed.write_to_fmt(&mut out)?;
+    /// assert_eq!(&out["<!-- cached -->".len()..], feed.to_string());
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_to_fmt<W: fmt::Write>(&self, writer: W) -> fmt::Result {
+        self.write_with_config(FmtWriter::new(writer), WriteConfig::default())
+            .map(|_| ())
+            .map_err(|_| fmt::Error)
+    }
+
+    /// Attempt to write this Atom feed to a writer using default `WriteConfig`, after checking
+    /// that it can be written as well-formed XML.
+    ///
+    /// Fails with `Error::InvalidContent` before anything is written if a field contains
+    /// characters which are not allowed in XML, xhtml text or content is not well-formed, or an
+    /// extension or namespace prefix is not a valid name. Other text is escaped when written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Error, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_id("urn:feed\u{0}");
+    ///
+    /// match feed.write_checked(Vec::new()) {
+    ///     Err(Error::InvalidContent { field }) => assert_eq!(field, "id"),
+    ///     other => panic!("unexpected result: {:?}", other),
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_checked<W: Write>(&self, writer: W) -> Result<W, Error> {
+        self.check_xml("")
+            .map_err(|field| Error::InvalidContent { field })?;
+        self.write_to(writer)
+    }
+
+    /// Attempt to write this Atom feed to a writer using default `WriteConfig`, returning the
+    /// writer together with the number of bytes written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let feed = Feed::default();
+    /// let (out, written) = feed.write_to_counted(Vec::new())?;
+    /// assert_eq!(out.len(), written);
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_to_counted<W: Write>(&self, writer: W) -> Result<(W, usize), Error> {
+        let writer = self.write_to(CountingWriter::new(writer))?;
+        Ok(writer.into_parts())
     }
 
     /// Attempt to write this Atom feed to a writer.
     ///
+    /// Unlike in `write_to`, the output is not buffered, so an unbuffered `File` or `TcpStream`
+    /// should be wrapped in a `BufWriter`.
+    ///
     /// # Examples
     ///
     /// ```
@@ -179,6 +844,7 @@ impl Feed {
     /// let config = WriteConfig {
     ///     write_document_declaration: false,
     ///     indent_size: Some(2),
+    ///     ..Default::default()
     /// };
     /// feed.write_with_config(&mut out, config)?;
     /// assert_eq!(&out, br#"<feed xmlns="http://www.w3.org/2005/Atom">
@@ -188,11 +854,22 @@ impl Feed {
     /// </feed>"#);
     /// # Ok(()) }
     /// ```
+    #[cfg(feature = "std")]
     pub fn write_with_config<W: Write>(
         &self,
         writer: W,
         write_config: WriteConfig,
     ) -> Result<W, Error> {
+        match write_config.line_ending {
+            LineEnding::Lf => self.write_xml(writer, write_config),
+            LineEnding::CrLf => self
+                .write_xml(CrLfWriter::new(writer), write_config)
+                .map(CrLfWriter::into_inner),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn write_xml<W: Write>(&self, writer: W, write_config: WriteConfig) -> Result<W, Error> {
         let mut writer = match write_config.indent_size {
             Some(indent_size) => Writer::new_with_indent(writer, b' ', indent_size),
             None => Writer::new(writer),
@@ -205,7 +882,26 @@ impl Feed {
                 .write_event(Event::Text(BytesText::from_escaped("\n")))
                 .map_err(XmlError::new)?;
         }
-        self.to_xml(&mut writer)?;
+        for item in &self.prolog {
+            let event = match item {
+                PrologItem::Comment(comment) => Event::Comment(BytesText::from_escaped(comment)),
+                PrologItem::ProcessingInstruction(pi) => Event::PI(BytesPI::new(pi.as_str())),
+            };
+            writer.write_event(event).map_err(XmlError::new)?;
+            writer
+                .write_event(Event::Text(BytesText::from_escaped("\n")))
+                .map_err(XmlError::new)?;
+        }
+        let default_generator;
+        let generator = match self.generator {
+            Some(ref generator) => Some(generator),
+            None if write_config.write_default_generator => {
+                default_generator = Generator::atom_syndication();
+                Some(&default_generator)
+            }
+            None => None,
+        };
+        self.to_xml_with_generator(&mut writer, generator, &write_config)?;
         Ok(writer.into_inner())
     }
 
@@ -290,6 +986,23 @@ impl Feed {
         &self.updated
     }
 
+    /// Return the last time that this feed was modified as seconds since the Unix epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    /// use atom_syndication::FixedDateTime;
+    /// use std::str::FromStr;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_updated(FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap());
+    /// assert_eq!(feed.updated_timestamp(), 1496520944);
+    /// ```
+    pub fn updated_timestamp(&self) -> i64 {
+        self.updated.timestamp()
+    }
+
     /// Set the last time that this feed was modified.
     ///
     /// # Examples
@@ -324,6 +1037,21 @@ impl Feed {
         self.authors.as_slice()
     }
 
+    /// Return a mutable reference to the authors of this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Person};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.authors_mut().push(Person::default());
+    /// assert_eq!(feed.authors().len(), 1);
+    /// ```
+    pub fn authors_mut(&mut self) -> &mut Vec<Person> {
+        &mut self.authors
+    }
+
     /// Set the authors of this feed.
     ///
     /// # Examples
@@ -341,19 +1069,72 @@ impl Feed {
         self.authors = authors.into();
     }
 
-    /// Return the categories this feed belongs to.
+    /// Return the distinct authors of this feed and all of its entries.
+    ///
+    /// Authors are considered the same if they have the same name and email address. Each is
+    /// returned once, as it first appears, starting with the authors of the feed.
     ///
     /// # Examples
     ///
     /// ```
-    /// use atom_syndication::{Feed, Category};
+    /// use atom_syndication::{Entry, Feed, Person};
+    ///
+    /// let person = |name: &str, uri: &str| Person {
+    ///     name: name.to_string(),
+    ///     uri: Some(uri.to_string()),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_authors(vec![person("Jane Doe", "http://a.example.com/"), person("John Doe", "")]);
     ///
     /// let mut feed = Feed::default();
-    /// feed.set_categories(vec![Category::default()]);
-    /// assert_eq!(feed.categories().len(), 1);
-    /// ```
-    pub fn categories(&self) -> &[Category] {
-        self.categories.as_slice()
+    /// feed.set_authors(vec![person("Jane Doe", "http://b.example.com/")]);
+    /// feed.set_entries(vec![entry.clone(), entry]);
+    ///
+    /// let authors = feed.unique_authors();
+    /// assert_eq!(authors.len(), 2);
+    /// assert_eq!(authors[0].uri(), Some("http://b.example.com/"));
+    /// assert_eq!(authors[1].name(), "John Doe");
+    /// ```
+    pub fn unique_authors(&self) -> Vec<&Person> {
+        let mut seen = BTreeSet::new();
+        let entry_authors = self.entries.iter().flat_map(|entry| &entry.authors);
+        self.authors
+            .iter()
+            .chain(entry_authors)
+            .filter(|person| seen.insert((person.name.as_str(), person.email.as_deref())))
+            .collect()
+    }
+
+    /// Return the categories this feed belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Category};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_categories(vec![Category::default()]);
+    /// assert_eq!(feed.categories().len(), 1);
+    /// ```
+    pub fn categories(&self) -> &[Category] {
+        self.categories.as_slice()
+    }
+
+    /// Return a mutable reference to the categories this feed belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Category};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.categories_mut().push(Category::default());
+    /// assert_eq!(feed.categories().len(), 1);
+    /// ```
+    pub fn categories_mut(&mut self) -> &mut Vec<Category> {
+        &mut self.categories
     }
 
     /// Set the categories this feed belongs to.
@@ -373,6 +1154,80 @@ impl Feed {
         self.categories = categories.into();
     }
 
+    /// Add a category to this feed unless an equal one is present already.
+    ///
+    /// Returns whether the category was added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Category, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// let category = Category { term: "technology".to_string(), ..Default::default() };
+    /// assert!(feed.add_category_unique(category.clone()));
+    /// assert!(!feed.add_category_unique(category));
+    /// assert_eq!(feed.categories().len(), 1);
+    /// ```
+    pub fn add_category_unique(&mut self, category: Category) -> bool {
+        if self.categories.contains(&category) {
+            false
+        } else {
+            self.categories.push(category);
+            true
+        }
+    }
+
+    /// Return the categories of this feed and its entries grouped by their scheme.
+    ///
+    /// Schemes are resolved against the `xml:base` of the feed, the entry and the category as
+    /// by `Category::resolved_scheme`. Categories without a scheme are grouped under `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Category, Entry, Feed};
+    ///
+    /// let category = |term: &str, scheme: Option<&str>| Category {
+    ///     term: term.to_string(),
+    ///     scheme: scheme.map(ToString::to_string),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let mut entry = Entry::default();
+    /// entry.set_categories(vec![category("rust", Some("/tags")), category("misc", None)]);
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_base("http://example.com/feed.xml".to_string());
+    /// feed.set_categories(vec![category("programming", Some("http://example.com/tags"))]);
+    /// feed.set_entries(vec![entry]);
+    ///
+    /// let groups = feed.categories_by_scheme();
+    /// assert_eq!(groups.len(), 2);
+    /// let tags = &groups[&Some("http://example.com/tags".to_string())];
+    /// assert_eq!(tags.iter().map(|c| c.term()).collect::<Vec<_>>(), vec!["programming", "rust"]);
+    /// assert_eq!(groups[&None][0].term(), "misc");
+    /// ```
+    pub fn categories_by_scheme(&self) -> BTreeMap<Option<String>, Vec<&Category>> {
+        let mut groups = BTreeMap::<_, Vec<_>>::new();
+        for category in &self.categories {
+            groups
+                .entry(category.resolved_scheme(self.base.as_deref()))
+                .or_default()
+                .push(category);
+        }
+        for entry in &self.entries {
+            let base = join_base(self.base.as_deref(), entry.base.as_deref());
+            for category in &entry.categories {
+                groups
+                    .entry(category.resolved_scheme(base.as_deref()))
+                    .or_default()
+                    .push(category);
+            }
+        }
+        groups
+    }
+
     /// Return the contributors to this feed.
     ///
     /// # Examples
@@ -388,6 +1243,21 @@ impl Feed {
         self.contributors.as_slice()
     }
 
+    /// Return a mutable reference to the contributors to this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Person};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.contributors_mut().push(Person::default());
+    /// assert_eq!(feed.contributors().len(), 1);
+    /// ```
+    pub fn contributors_mut(&mut self) -> &mut Vec<Person> {
+        &mut self.contributors
+    }
+
     /// Set the contributors to this feed.
     ///
     /// # Examples
@@ -452,6 +1322,24 @@ impl Feed {
         self.icon.as_deref()
     }
 
+    /// Return the URL of the icon parsed as a `Url`.
+    ///
+    /// Returns `None` if it is not set. The stored string is left untouched if it fails to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_icon("http://example.com/icon.png".to_string());
+    /// assert_eq!(feed.icon_url().unwrap().unwrap().as_str(), "http://example.com/icon.png");
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn icon_url(&self) -> Option<Result<url::Url, url::ParseError>> {
+        self.icon.as_deref().map(url::Url::parse)
+    }
+
     /// Set the icon for this feed.
     ///
     /// # Examples
@@ -484,6 +1372,22 @@ impl Feed {
         self.links.as_slice()
     }
 
+    /// Return a mutable reference to the Web pages related to this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_links(vec![Link::default()]);
+    /// feed.links_mut()[0].set_href("http://example.com/");
+    /// assert_eq!(feed.links()[0].href(), "http://example.com/");
+    /// ```
+    pub fn links_mut(&mut self) -> &mut Vec<Link> {
+        &mut self.links
+    }
+
     /// Set the Web pages related to this feed.
     ///
     /// # Examples
@@ -501,6 +1405,117 @@ impl Feed {
         self.links = links.into();
     }
 
+    /// Return the links of this feed with the given relation, in document order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link, LinkRel};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_links(vec![
+    ///     Link { rel: "edit".into(), href: "http://example.com/edit".into(), ..Default::default() },
+    ///     Link { href: "http://example.com/".into(), ..Default::default() },
+    /// ]);
+    /// let hrefs = feed.links_by_rel(LinkRel::Edit).map(Link::href).collect::<Vec<_>>();
+    /// assert_eq!(hrefs, vec!["http://example.com/edit"]);
+    /// ```
+    pub fn links_by_rel(&self, rel: LinkRel) -> impl Iterator<Item = &Link> {
+        self.links.iter().filter(move |link| link.relation() == rel)
+    }
+
+    /// Return the first link of this feed with `rel="license"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link, LinkRel};
+    ///
+    /// let mut feed = Feed::default();
+    /// assert!(feed.license_link().is_none());
+    ///
+    /// feed.set_links(vec![Link {
+    ///     rel: LinkRel::License.into(),
+    ///     href: "https://creativecommons.org/licenses/by/4.0/".into(),
+    ///     ..Default::default()
+    /// }]);
+    /// assert_eq!(feed.license_link().map(Link::href), Some("https://creativecommons.org/licenses/by/4.0/"));
+    /// ```
+    pub fn license_link(&self) -> Option<&Link> {
+        self.links_by_rel(LinkRel::License).next()
+    }
+
+    /// Add a link to this feed unless an equal one is present already.
+    ///
+    /// Returns whether the link was added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Link, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// let link = Link { rel: "self".to_string(), href: "http://example.com/feed".to_string(), ..Default::default() };
+    /// assert!(feed.add_link_unique(link.clone()));
+    /// assert!(!feed.add_link_unique(link));
+    /// assert_eq!(feed.links().len(), 1);
+    /// ```
+    pub fn add_link_unique(&mut self, link: Link) -> bool {
+        if self.links.contains(&link) {
+            false
+        } else {
+            self.links.push(link);
+            true
+        }
+    }
+
+    /// Return the links of this feed with `rel="hub"` advertising a WebSub hub.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_links(vec![
+    ///     Link { rel: "hub".into(), href: "http://example.com/hub".into(), ..Default::default() },
+    ///     Link { rel: "self".into(), href: "http://example.com/feed".into(), ..Default::default() },
+    /// ]);
+    /// let hubs = feed.hub_links().map(Link::href).collect::<Vec<_>>();
+    /// assert_eq!(hubs, vec!["http://example.com/hub"]);
+    /// ```
+    pub fn hub_links(&self) -> impl Iterator<Item = &Link> {
+        self.links.iter().filter(|link| link.rel == "hub")
+    }
+
+    /// Return the `(hub, self)` URIs needed to subscribe to this feed with WebSub.
+    ///
+    /// The first hub link and the first `rel="self"` link are used. Returns `None` if either of
+    /// them is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Link};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_links(vec![
+    ///     Link { rel: "hub".into(), href: "http://example.com/hub".into(), ..Default::default() },
+    /// ]);
+    /// assert_eq!(feed.websub_endpoints(), None);
+    ///
+    /// feed.links.push(Link { rel: "self".into(), href: "http://example.com/feed".into(), ..Default::default() });
+    /// assert_eq!(
+    ///     feed.websub_endpoints(),
+    ///     Some(("http://example.com/hub".to_string(), "http://example.com/feed".to_string()))
+    /// );
+    /// ```
+    pub fn websub_endpoints(&self) -> Option<(String, String)> {
+        let hub = self.hub_links().next()?;
+        let this = self.links.iter().find(|link| link.rel == "self")?;
+        Some((hub.href.clone(), this.href.clone()))
+    }
+
     /// Return the logo for this feed.
     ///
     /// # Examples
@@ -516,6 +1531,24 @@ impl Feed {
         self.logo.as_deref()
     }
 
+    /// Return the URL of the logo parsed as a `Url`.
+    ///
+    /// Returns `None` if it is not set. The stored string is left untouched if it fails to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_logo("http://example.com/logo.png".to_string());
+    /// assert_eq!(feed.logo_url().unwrap().unwrap().as_str(), "http://example.com/logo.png");
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn logo_url(&self) -> Option<Result<url::Url, url::ParseError>> {
+        self.logo.as_deref().map(url::Url::parse)
+    }
+
     /// Set the logo for this feed.
     ///
     /// # Examples
@@ -612,6 +1645,22 @@ impl Feed {
         self.entries.as_slice()
     }
 
+    /// Return a mutable reference to the entries in this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Entry};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_entries(vec![Entry::default()]);
+    /// feed.entries_mut()[0].set_title("Entry Title");
+    /// assert_eq!(feed.entries()[0].title(), "Entry Title");
+    /// ```
+    pub fn entries_mut(&mut self) -> &mut Vec<Entry> {
+        &mut self.entries
+    }
+
     /// Set the entries in this feed.
     ///
     /// # Examples
@@ -629,6 +1678,215 @@ impl Feed {
         self.entries = entries.into();
     }
 
+    /// Remove all entries from this feed and return them, leaving the rest of the feed as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Entry};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_entries(vec![Entry::default()]);
+    /// let entries = feed.take_entries();
+    /// assert_eq!(entries.len(), 1);
+    /// assert!(feed.entries().is_empty());
+    /// ```
+    pub fn take_entries(&mut self) -> Vec<Entry> {
+        core::mem::take(&mut self.entries)
+    }
+
+    /// Reserve capacity for at least `additional` more entries in this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.reserve_entries(10);
+    /// assert!(feed.entries.capacity() >= 10);
+    /// ```
+    pub fn reserve_entries(&mut self, additional: usize) {
+        self.entries.reserve(additional);
+    }
+
+    /// Return the number of entries in this feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// assert_eq!(feed.entry_count(), 0);
+    ///
+    /// feed.set_entries(vec![Entry::default()]);
+    /// assert_eq!(feed.entry_count(), 1);
+    /// ```
+    pub fn entry_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return whether this feed contains any entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// assert!(!feed.has_entries());
+    ///
+    /// feed.set_entries(vec![Entry::default()]);
+    /// assert!(feed.has_entries());
+    /// ```
+    pub fn has_entries(&self) -> bool {
+        !self.entries.is_empty()
+    }
+
+    /// Set the last time that this feed was modified to the latest `updated` time of its
+    /// entries.
+    ///
+    /// The feed is left unchanged if it has no entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed, FixedDateTime};
+    /// use std::str::FromStr;
+    ///
+    /// let mut first = Entry::default();
+    /// first.set_updated(FixedDateTime::from_str("2017-06-03T15:15:44-05:00").unwrap());
+    /// let mut second = Entry::default();
+    /// second.set_updated(FixedDateTime::from_str("2017-06-04T09:00:00+02:00").unwrap());
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_entries(vec![first, second]);
+    /// feed.recompute_updated();
+    /// assert_eq!(feed.updated().to_rfc3339(), "2017-06-04T09:00:00+02:00");
+    /// ```
+    pub fn recompute_updated(&mut self) {
+        if let Some(updated) = self.entries.iter().map(|entry| entry.updated).max() {
+            self.updated = updated;
+        }
+    }
+
+    /// Return the first entry of this feed with the given `id`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_entries(vec![Entry { id: "urn:uuid:1".to_string(), ..Default::default() }]);
+    /// assert!(feed.entry_by_id("urn:uuid:1").is_some());
+    /// assert!(feed.entry_by_id("urn:uuid:2").is_none());
+    /// ```
+    pub fn entry_by_id(&self, id: &str) -> Option<&Entry> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    /// Return the first entry of this feed with the given `id` mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_entries(vec![Entry { id: "urn:uuid:1".to_string(), ..Default::default() }]);
+    /// feed.entry_by_id_mut("urn:uuid:1").unwrap().set_title("Entry Title");
+    /// assert_eq!(feed.entries()[0].title(), "Entry Title");
+    /// ```
+    pub fn entry_by_id_mut(&mut self, id: &str) -> Option<&mut Entry> {
+        self.entries.iter_mut().find(|entry| entry.id == id)
+    }
+
+    /// Compare the entries of this feed with those of a previous version of it.
+    ///
+    /// Entries are matched by their ID. A matched entry is updated if its `updated` time
+    /// differs. Only the first of several entries with the same ID is considered, and entries
+    /// without an ID are only matched by an equal entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed, FixedDateTime};
+    /// use std::str::FromStr;
+    ///
+    /// let entry = |id: &str, updated: &str| {
+    ///     let mut entry = Entry::default();
+    ///     entry.set_id(id);
+    ///     entry.set_updated(FixedDateTime::from_str(updated).unwrap());
+    ///     entry
+    /// };
+    ///
+    /// let previous: Feed = vec![
+    ///     entry("urn:1", "2017-06-01T00:00:00Z"),
+    ///     entry("urn:2", "2017-06-01T00:00:00Z"),
+    ///     entry("urn:3", "2017-06-01T00:00:00Z"),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    /// let current: Feed = vec![
+    ///     entry("urn:2", "2017-06-01T00:00:00Z"),
+    ///     entry("urn:3", "2017-06-02T00:00:00Z"),
+    ///     entry("urn:4", "2017-06-02T00:00:00Z"),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let diff = current.diff(&previous);
+    /// assert_eq!(diff.added.iter().map(|e| e.id()).collect::<Vec<_>>(), vec!["urn:4"]);
+    /// assert_eq!(diff.updated.iter().map(|e| e.id()).collect::<Vec<_>>(), vec!["urn:3"]);
+    /// assert_eq!(diff.removed.iter().map(|e| e.id()).collect::<Vec<_>>(), vec!["urn:1"]);
+    /// ```
+    pub fn diff<'a>(&'a self, previous: &'a Feed) -> FeedDiff<'a> {
+        let mut first_by_id = BTreeMap::new();
+        for (index, entry) in previous.entries.iter().enumerate() {
+            if !entry.id.is_empty() {
+                first_by_id.entry(entry.id.as_str()).or_insert(index);
+            }
+        }
+
+        let mut diff = FeedDiff::default();
+        let mut matched = alloc::vec![false; previous.entries.len()];
+        let mut seen = BTreeSet::new();
+        for entry in &self.entries {
+            let index = if entry.id.is_empty() {
+                (0..previous.entries.len()).find(|&index| {
+                    let other = &previous.entries[index];
+                    !matched[index] && other.id.is_empty() && other == entry
+                })
+            } else if seen.insert(entry.id.as_str()) {
+                first_by_id.get(entry.id.as_str()).copied()
+            } else {
+                continue;
+            };
+            match index {
+                Some(index) => {
+                    matched[index] = true;
+                    if previous.entries[index].updated != entry.updated {
+                        diff.updated.push(entry);
+                    }
+                }
+                None => diff.added.push(entry),
+            }
+        }
+
+        diff.removed = previous
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|&(index, entry)| {
+                !matched[index] && (entry.id.is_empty() || first_by_id[entry.id.as_str()] == index)
+            })
+            .map(|(_, entry)| entry)
+            .collect();
+        diff
+    }
+
     /// Return the extensions for this feed.
     ///
     /// # Examples
@@ -738,86 +1996,234 @@ impl Feed {
     {
         self.lang = lang.into();
     }
-}
 
-impl FromXml for Feed {
-    fn from_xml<B: BufRead>(
-        reader: &mut Reader<B>,
+    /// Return the `xml:id` of the feed.
+    pub fn xml_id(&self) -> Option<&str> {
+        self.xml_id.as_deref()
+    }
+
+    /// Set the `xml:id` of the feed.
+    pub fn set_xml_id<V>(&mut self, xml_id: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.xml_id = xml_id.into();
+    }
+
+    /// Return a copy of this feed's metadata without any of its entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Entry, Feed};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_title("Feed Title");
+    /// feed.set_entries(vec![Entry::default()]);
+    ///
+    /// let page = feed.metadata_only();
+    /// assert_eq!(page.title(), "Feed Title");
+    /// assert!(page.entries().is_empty());
+    /// ```
+    pub fn metadata_only(&self) -> Feed {
+        Feed {
+            title: self.title.clone(),
+            id: self.id.clone(),
+            updated: self.updated,
+            authors: self.authors.clone(),
+            categories: self.categories.clone(),
+            contributors: self.contributors.clone(),
+            generator: self.generator.clone(),
+            icon: self.icon.clone(),
+            links: self.links.clone(),
+            logo: self.logo.clone(),
+            rights: self.rights.clone(),
+            subtitle: self.subtitle.clone(),
+            entries: Vec::new(),
+            extensions: self.extensions.clone(),
+            namespaces: self.namespaces.clone(),
+            base: self.base.clone(),
+            lang: self.lang.clone(),
+            xml_id: self.xml_id.clone(),
+            prolog: self.prolog.clone(),
+            comments: self.comments.clone(),
+        }
+    }
+}
+
+/// The children of the element which may only appear once.
+#[cfg(feature = "std")]
+const FEED_SINGLETONS: &[&str] = &[
+    "title",
+    "id",
+    "updated",
+    "generator",
+    "icon",
+    "logo",
+    "rights",
+    "subtitle",
+];
+
+/// The changes between two versions of a feed as returned by [`Feed::diff`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeedDiff<'a> {
+    /// Entries which are not in the previous version.
+    pub added: Vec<&'a Entry>,
+    /// Entries whose `updated` time differs from the previous version.
+    pub updated: Vec<&'a Entry>,
+    /// Entries of the previous version which are no longer present.
+    pub removed: Vec<&'a Entry>,
+}
+
+#[cfg(feature = "std")]
+impl Feed {
+    /// Creates a feed holding only the attributes of its `<feed>` element.
+    fn from_attributes<B: BufRead>(
+        reader: &Reader<B>,
         mut atts: Attributes<'_>,
+        opts: &ParseOpts,
     ) -> Result<Self, Error> {
         let mut feed = Feed::default();
-        let mut buf = Vec::new();
+        let mut default_ns = None;
 
         for att in atts.with_checks(false).flatten() {
             match decode(att.key.as_ref(), reader)? {
+                Cow::Borrowed("xmlns") => {
+                    default_ns = Some(attr_value(&att, reader, opts)?.into_owned())
+                }
                 Cow::Borrowed("xml:base") => {
-                    feed.base = Some(attr_value(&att, reader)?.to_string())
+                    feed.base = Some(attr_value(&att, reader, opts)?.into_owned())
                 }
                 Cow::Borrowed("xml:lang") => {
-                    feed.lang = Some(attr_value(&att, reader)?.to_string())
+                    feed.lang = Some(attr_value(&att, reader, opts)?.into_owned())
+                }
+                Cow::Borrowed("xml:id") => {
+                    feed.xml_id = Some(attr_value(&att, reader, opts)?.into_owned())
                 }
                 Cow::Borrowed("xmlns:dc") => {}
                 key => {
                     if let Some(ns) = key.strip_prefix("xmlns:") {
                         feed.namespaces
-                            .insert(ns.to_string(), attr_value(&att, reader)?.to_string());
+                            .insert(ns.to_string(), attr_value(&att, reader, opts)?.into_owned());
                     }
                 }
             }
         }
 
+        if opts.require_atom_namespace && default_ns.as_deref() != Some(ATOM_NS) {
+            return Err(Error::WrongNamespace { found: default_ns });
+        }
+
+        Ok(feed)
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromXml for Feed {
+    fn from_xml<B: BufRead>(
+        reader: &mut Reader<B>,
+        atts: Attributes<'_>,
+        opts: &ParseOpts,
+    ) -> Result<Self, Error> {
+        Feed::from_xml_filtered(reader, atts, opts, &mut |_| true)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Feed {
+    /// Reads a feed like `from_xml`, keeping only the entries accepted by `filter`.
+    fn from_xml_filtered<B: BufRead>(
+        reader: &mut Reader<B>,
+        atts: Attributes<'_>,
+        opts: &ParseOpts,
+        filter: &mut dyn FnMut(&Entry) -> bool,
+    ) -> Result<Self, Error> {
+        let mut feed = Feed::from_attributes(reader, atts, opts)?;
+        let mut updated = None;
+        let mut entries_read = 0;
+        let mut seen = Vec::new();
+        let mut buf = Vec::new();
+
         loop {
-            match reader.read_event_into(&mut buf).map_err(XmlError::new)? {
-                Event::Start(element) => match decode(element.name().as_ref(), reader)? {
+            let event = reader.read_event_into(&mut buf).map_err(XmlError::new)?;
+            let empty = matches!(event, Event::Empty(_));
+            match event {
+                Event::Start(element) | Event::Empty(element) => match check_duplicate(
+                    decode(element.name().as_ref(), reader)?,
+                    FEED_SINGLETONS,
+                    &mut seen,
+                    opts,
+                )? {
                     Cow::Borrowed("title") => {
-                        feed.title = Text::from_xml(reader, element.attributes())?
+                        feed.title = Text::from_element(reader, &element, empty, opts)?
+                    }
+                    Cow::Borrowed("id") => {
+                        feed.id = element_text(reader, empty, opts)?.unwrap_or_default()
                     }
-                    Cow::Borrowed("id") => feed.id = atom_text(reader)?.unwrap_or_default(),
                     Cow::Borrowed("updated") => {
-                        feed.updated = atom_datetime(reader)?.unwrap_or_else(default_fixed_datetime)
+                        updated = element_datetime(reader, empty, "updated", opts)?
                     }
                     Cow::Borrowed("author") => feed
                         .authors
-                        .push(Person::from_xml(reader, element.attributes())?),
+                        .push(Person::from_element(reader, &element, empty, opts)?),
                     Cow::Borrowed("category") => {
-                        feed.categories.push(Category::from_xml(reader, &element)?);
-                        skip(element.name(), reader)?;
+                        feed.categories
+                            .push(Category::from_xml(reader, &element, opts)?);
+                        if !empty {
+                            skip(element.name(), reader)?;
+                        }
                     }
                     Cow::Borrowed("contributor") => feed
                         .contributors
-                        .push(Person::from_xml(reader, element.attributes())?),
+                        .push(Person::from_element(reader, &element, empty, opts)?),
                     Cow::Borrowed("generator") => {
-                        feed.generator = Some(Generator::from_xml(reader, element.attributes())?)
+                        feed.generator =
+                            Some(Generator::from_element(reader, &element, empty, opts)?)
                     }
-                    Cow::Borrowed("icon") => feed.icon = atom_text(reader)?,
+                    Cow::Borrowed("icon") => feed.icon = element_text(reader, empty, opts)?,
                     Cow::Borrowed("link") => {
-                        feed.links.push(Link::from_xml(reader, &element)?);
-                        skip(element.name(), reader)?;
+                        if let Some(link) = Link::from_xml(reader, &element, opts)? {
+                            feed.links.push(link);
+                        }
+                        if !empty {
+                            skip(element.name(), reader)?;
+                        }
                     }
-                    Cow::Borrowed("logo") => feed.logo = atom_text(reader)?,
+                    Cow::Borrowed("logo") => feed.logo = element_text(reader, empty, opts)?,
                     Cow::Borrowed("rights") => {
-                        feed.rights = Some(Text::from_xml(reader, element.attributes())?)
+                        feed.rights = Some(Text::from_element(reader, &element, empty, opts)?)
                     }
                     Cow::Borrowed("subtitle") => {
-                        feed.subtitle = Some(Text::from_xml(reader, element.attributes())?)
+                        feed.subtitle = Some(Text::from_element(reader, &element, empty, opts)?)
+                    }
+                    Cow::Borrowed("entry") => {
+                        let mut entry = Entry::from_element(reader, &element, empty, opts)
+                            .map_err(|err| err.in_entry(entries_read))?;
+                        entries_read += 1;
+                        entry.apply_base(feed.base.as_deref());
+                        if filter(&entry) {
+                            feed.entries.push(entry);
+                        }
                     }
-                    Cow::Borrowed("entry") => feed
-                        .entries
-                        .push(Entry::from_xml(reader, element.attributes())?),
                     n => {
                         if let Some((ns, name)) = extension_name(n.as_ref()) {
                             parse_extension(
                                 reader,
-                                element.attributes(),
+                                &element,
+                                empty,
                                 ns,
                                 name,
                                 &mut feed.extensions,
+                                opts,
                             )?;
-                        } else {
+                        } else if !empty {
                             skip(element.name(), reader)?;
                         }
                     }
                 },
+                Event::Comment(comment) if opts.preserve_comments => {
+                    feed.comments.push(decode(&comment, reader)?.into_owned());
+                }
                 Event::End(_) => break,
                 Event::Eof => return Err(Error::Eof),
                 _ => {}
@@ -826,18 +2232,59 @@ impl FromXml for Feed {
             buf.clear();
         }
 
+        feed.updated = match updated {
+            Some(updated) => updated,
+            None => opts.missing_updated.resolve()?,
+        };
+        let base = feed.base.as_deref();
+        feed.title.apply_base(base);
+        for text in feed.rights.iter_mut().chain(feed.subtitle.iter_mut()) {
+            text.apply_base(base);
+        }
+        for link in &mut feed.links {
+            link.apply_base(base);
+        }
+
+        Ok(feed)
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromEmptyXml for Feed {
+    fn from_empty_xml<B: BufRead>(
+        reader: &mut Reader<B>,
+        atts: Attributes<'_>,
+        opts: &ParseOpts,
+    ) -> Result<Self, Error> {
+        let mut feed = Feed::from_attributes(reader, atts, opts)?;
+        feed.updated = opts.missing_updated.resolve()?;
         Ok(feed)
     }
 }
 
+#[cfg(feature = "std")]
 impl ToXml for Feed {
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        self.to_xml_with_generator(writer, self.generator.as_ref(), &WriteConfig::default())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Feed {
+    /// Writes this feed according to the options of `config`, with the given generator in place
+    /// of its own one.
+    fn to_xml_with_generator<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        generator: Option<&Generator>,
+        config: &WriteConfig,
+    ) -> Result<(), XmlError> {
         let name = "feed";
         let mut element = BytesStart::new(name);
-        element.push_attribute(("xmlns", "http://www.w3.org/2005/Atom"));
+        element.push_attribute(("xmlns", ATOM_NS));
 
         for (ns, uri) in &self.namespaces {
-            element.push_attribute((format!("xmlns:{}", ns).as_bytes(), uri.as_bytes()));
+            element.push_attribute((format!("xmlns:{}", ns).as_str(), uri.as_str()));
         }
 
         if let Some(ref base) = self.base {
@@ -848,17 +2295,30 @@ impl ToXml for Feed {
             element.push_attribute(("xml:lang", lang.as_str()));
         }
 
+        if let Some(ref xml_id) = self.xml_id {
+            element.push_attribute(("xml:id", xml_id.as_str()));
+        }
+
         writer
             .write_event(Event::Start(element))
             .map_err(XmlError::new)?;
-        writer.write_object_named(&self.title, "title")?;
-        writer.write_text_element("id", &self.id)?;
-        writer.write_text_element("updated", &self.updated.to_rfc3339())?;
+
+        for comment in &self.comments {
+            writer
+                .write_event(Event::Comment(BytesText::from_escaped(comment)))
+                .map_err(XmlError::new)?;
+        }
+
+        if !config.rfc_element_order {
+            writer.write_object_named(&self.title, "title")?;
+            writer.write_text_element("id", &self.id)?;
+            writer.write_text_element("updated", &format_datetime(&self.updated, config))?;
+        }
         writer.write_objects_named(&self.authors, "author")?;
         writer.write_objects(&self.categories)?;
         writer.write_objects_named(&self.contributors, "contributor")?;
 
-        if let Some(ref generator) = self.generator {
+        if let Some(generator) = generator {
             writer.write_object(generator)?;
         }
 
@@ -866,7 +2326,13 @@ impl ToXml for Feed {
             writer.write_text_element("icon", icon)?;
         }
 
-        writer.write_objects(&self.links)?;
+        if config.rfc_element_order {
+            writer.write_text_element("id", &self.id)?;
+        }
+
+        for link in &self.links {
+            link.to_xml_with_config(writer, config)?;
+        }
 
         if let Some(ref logo) = self.logo {
             writer.write_text_element("logo", logo)?;
@@ -880,7 +2346,10 @@ impl ToXml for Feed {
             writer.write_object_named(subtitle, "subtitle")?;
         }
 
-        writer.write_objects(&self.entries)?;
+        if config.rfc_element_order {
+            writer.write_object_named(&self.title, "title")?;
+            writer.write_text_element("updated", &format_datetime(&self.updated, config))?;
+        }
 
         for map in self.extensions.values() {
             for extensions in map.values() {
@@ -888,6 +2357,10 @@ impl ToXml for Feed {
             }
         }
 
+        for entry in &self.entries {
+            entry.to_xml_with_config(writer, config)?;
+        }
+
         writer
             .write_event(Event::End(BytesEnd::new(name)))
             .map_err(XmlError::new)?;
@@ -896,19 +2369,58 @@ impl ToXml for Feed {
     }
 }
 
+#[cfg(feature = "std")]
 impl FromStr for Feed {
     type Err = Error;
 
+    /// Reads a feed from a string. A leading byte order mark, which `std::fs::read_to_string`
+    /// keeps, is skipped by the XML reader.
     fn from_str(s: &str) -> Result<Self, Error> {
         Feed::read_from(s.as_bytes())
     }
 }
 
-impl ToString for Feed {
-    fn to_string(&self) -> String {
-        let buf = self.write_to(Vec::new()).unwrap_or_default();
-        // this unwrap should be safe since the bytes written from the Feed are all valid utf8
-        String::from_utf8(buf).unwrap()
+/// Reads a feed from raw bytes like `Feed::read_from`.
+///
+/// Unlike `str::parse`, the bytes are decoded according to their byte order mark or encoding
+/// declaration, so documents in encodings other than UTF-8 are read correctly.
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::Feed;
+///
+/// let xml = b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?><feed><title>Caf\xe9</title></feed>";
+/// let feed = Feed::try_from(&xml[..]).unwrap();
+/// assert_eq!(feed.title(), "Caf\u{e9}");
+/// ```
+#[cfg(feature = "std")]
+impl TryFrom<&[u8]> for Feed {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        Feed::read_from(bytes)
+    }
+}
+
+impl Extend<Entry> for Feed {
+    fn extend<I: IntoIterator<Item = Entry>>(&mut self, iter: I) {
+        self.entries.extend(iter);
+    }
+}
+
+impl FromIterator<Entry> for Feed {
+    fn from_iter<I: IntoIterator<Item = Entry>>(iter: I) -> Self {
+        let mut feed = Feed::default();
+        feed.extend(iter);
+        feed
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for Feed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_to_fmt(f)
     }
 }
 
@@ -932,6 +2444,9 @@ impl Default for Feed {
             namespaces: BTreeMap::default(),
             base: None,
             lang: None,
+            xml_id: None,
+            prolog: Vec::new(),
+            comments: Vec::new(),
         }
     }
 }
@@ -942,9 +2457,148 @@ impl FeedBuilder {
     pub fn build(&self) -> Feed {
         self.build_impl().unwrap()
     }
+
+    /// Builds a new `Feed`, checking that the namespace prefix of every extension element is
+    /// declared in its `namespaces`.
+    ///
+    /// The extensions of the feed and its entries are checked, including the children of
+    /// extension elements. Fails with `Error::UndeclaredNamespace` otherwise, as the
+    /// written feed would not be namespace-well-formed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Error, FeedBuilder};
+    /// use atom_syndication::extension::ExtensionBuilder;
+    ///
+    /// let mut builder = FeedBuilder::default();
+    /// builder.extension((
+    ///     "itunes".to_string(),
+    ///     [(
+    ///         "author".to_string(),
+    ///         vec![ExtensionBuilder::default().name("itunes:author").build()],
+    ///     )]
+    ///     .into_iter()
+    ///     .collect(),
+    /// ));
+    /// match builder.try_build() {
+    ///     Err(Error::UndeclaredNamespace { prefix }) => assert_eq!(prefix, "itunes"),
+    ///     other => panic!("unexpected result: {:?}", other),
+    /// }
+    ///
+    /// builder.namespace((
+    ///     "itunes".to_string(),
+    ///     "http://www.itunes.com/dtds/podcast-1.0.dtd".to_string(),
+    /// ));
+    /// assert!(builder.try_build().is_ok());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn try_build(&self) -> Result<Feed, Error> {
+        let feed = self.build();
+        let entries = feed.entries.iter().map(|entry| &entry.extensions);
+        for extensions in core::iter::once(&feed.extensions).chain(entries) {
+            if let Some(prefix) = undeclared_prefix(extensions, &feed.namespaces) {
+                return Err(Error::UndeclaredNamespace {
+                    prefix: prefix.to_string(),
+                });
+            }
+        }
+        Ok(feed)
+    }
+
+    clear_methods! {
+        FeedBuilder => feed {
+            clear_authors: authors, author(Person);
+            clear_categories: categories, category(Category);
+            clear_contributors: contributors, contributor(Person);
+            clear_links: links, link(Link);
+            clear_entries: entries, entry(Entry);
+        }
+    }
+
+    /// Adds an extension element with a text value, keeping the extensions set so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::FeedBuilder;
+    ///
+    /// let feed = FeedBuilder::default()
+    ///     .simple_extension("dc", "creator", "John Doe")
+    ///     .simple_extension("dc", "creator", "Jane Doe")
+    ///     .build();
+    /// let creators = &feed.extensions()["dc"]["creator"];
+    /// assert_eq!(creators.len(), 2);
+    /// assert_eq!(creators[0].name(), "dc:creator");
+    /// assert_eq!(creators[1].value(), Some("Jane Doe"));
+    /// ```
+    pub fn simple_extension<P, N, V>(&mut self, prefix: P, name: N, value: V) -> &mut Self
+    where
+        P: Into<String>,
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.merge_extensions(simple_extension(prefix.into(), name.into(), value.into()))
+    }
+
+    /// Adds the given extensions to the extensions set so far.
+    ///
+    /// Unlike `extensions`, which replaces the whole map, and `extension`, which replaces all
+    /// elements of a prefix, elements sharing a prefix and local name are appended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::extension::{ExtensionBuilder, ExtensionMap};
+    /// use atom_syndication::FeedBuilder;
+    ///
+    /// let mut extensions = ExtensionMap::new();
+    /// extensions
+    ///     .entry("dc".to_string())
+    ///     .or_default()
+    ///     .insert(
+    ///         "subject".to_string(),
+    ///         vec![ExtensionBuilder::default().name("dc:subject").value("Technology".to_string()).build()],
+    ///     );
+    ///
+    /// let feed = FeedBuilder::default()
+    ///     .simple_extension("dc", "creator", "John Doe")
+    ///     .merge_extensions(extensions)
+    ///     .build();
+    /// assert_eq!(feed.extensions()["dc"].len(), 2);
+    /// ```
+    pub fn merge_extensions(&mut self, extensions: ExtensionMap) -> &mut Self {
+        merge_extensions(
+            self.extensions.get_or_insert_with(Default::default),
+            extensions,
+        );
+        self
+    }
+
+    /// Sets the last time the feed was modified from an RFC 3339 timestamp.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::FeedBuilder;
+    ///
+    /// # fn main() -> Result<(), atom_syndication::Error> {
+    /// let feed = FeedBuilder::default()
+    ///     .updated_rfc3339("2017-06-03T15:15:44-05:00")?
+    ///     .build();
+    /// assert_eq!(feed.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    ///
+    /// assert!(FeedBuilder::default().updated_rfc3339("yesterday").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn updated_rfc3339(&mut self, updated: &str) -> Result<&mut Self, Error> {
+        self.updated = Some(parse_rfc3339("updated", updated)?);
+        Ok(self)
+    }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test {
     use super::*;
 
@@ -974,6 +2628,103 @@ mod test {
         assert_eq!(loaded_feed.lang(), Some("fr_FR"));
     }
 
+    #[test]
+    fn test_diff_duplicate_and_missing_ids() {
+        let entry = |id: &str, title: &str| Entry {
+            id: id.to_string(),
+            title: title.into(),
+            ..Default::default()
+        };
+        let previous: Feed = vec![
+            entry("urn:1", "first"),
+            entry("urn:1", "duplicate"),
+            entry("", "anonymous"),
+            entry("", "removed"),
+        ]
+        .into_iter()
+        .collect();
+        let current: Feed = vec![
+            entry("", "anonymous"),
+            entry("urn:1", "first"),
+            entry("urn:1", "duplicate"),
+            entry("", "new"),
+        ]
+        .into_iter()
+        .collect();
+
+        let diff = current.diff(&previous);
+        let titles = |entries: &[&Entry]| {
+            entries
+                .iter()
+                .map(|entry| entry.title.value.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(titles(&diff.added), vec!["new"]);
+        assert!(diff.updated.is_empty());
+        assert_eq!(titles(&diff.removed), vec!["removed"]);
+        assert_eq!(current.diff(&current), FeedDiff::default());
+    }
+
+    #[test]
+    fn test_xml_id() {
+        let mut entry = Entry::default();
+        entry.set_xml_id(Some("entry-1".into()));
+        let mut feed = Feed::default();
+        feed.set_xml_id(Some("feed".into()));
+        feed.set_entries(vec![entry]);
+        let xml_fragment = r#"<?xml version="1.0"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xml:id="feed"><title></title><id></id><updated>1970-01-01T00:00:00+00:00</updated><entry xml:id="entry-1"><title></title><id></id><updated>1970-01-01T00:00:00+00:00</updated></entry></feed>"#;
+        assert_eq!(feed.to_string(), xml_fragment);
+        let loaded_feed = Feed::read_from(xml_fragment.as_bytes()).unwrap();
+        assert_eq!(loaded_feed, feed);
+        assert_eq!(loaded_feed.xml_id(), Some("feed"));
+        assert_eq!(loaded_feed.entries()[0].xml_id(), Some("entry-1"));
+    }
+
+    #[test]
+    fn test_extend_and_collect() {
+        let entry = |id: &str| Entry {
+            id: id.to_string(),
+            ..Default::default()
+        };
+
+        let mut feed = vec![entry("1"), entry("2")].into_iter().collect::<Feed>();
+        feed.extend(vec![entry("3")]);
+
+        let ids = feed.entries().iter().map(Entry::id).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["1", "2", "3"]);
+        assert_eq!(feed.metadata_only(), Feed::default());
+    }
+
+    #[test]
+    fn test_preserve_comments() {
+        let xml_fragment = r#"<?xml version="1.0"?>
+<!-- generated by Example -->
+<?xml-stylesheet href="feed.xsl" type="text/xsl"?>
+<feed xmlns="http://www.w3.org/2005/Atom"><!-- signature --><title></title><id></id><updated>1970-01-01T00:00:00+00:00</updated></feed>"#;
+
+        let feed = Feed::read_from(xml_fragment.as_bytes()).unwrap();
+        assert!(feed.prolog.is_empty());
+        assert!(feed.comments.is_empty());
+
+        let opts = ParseOpts {
+            preserve_comments: true,
+            ..Default::default()
+        };
+        let feed = Feed::read_from_opts(xml_fragment.as_bytes(), &opts).unwrap();
+        assert_eq!(
+            feed.prolog,
+            vec![
+                PrologItem::Comment(" generated by Example ".to_string()),
+                PrologItem::ProcessingInstruction(
+                    r#"xml-stylesheet href="feed.xsl" type="text/xsl""#.to_string()
+                ),
+            ]
+        );
+        assert_eq!(feed.comments, vec![" signature "]);
+        assert_eq!(feed.to_string(), xml_fragment);
+    }
+
     #[test]
     fn test_write_no_decl() {
         let feed = Feed::default();
@@ -983,6 +2734,7 @@ mod test {
                 WriteConfig {
                     write_document_declaration: false,
                     indent_size: None,
+                    ..Default::default()
                 },
             )
             .unwrap();
@@ -1001,6 +2753,7 @@ mod test {
                 WriteConfig {
                     write_document_declaration: true,
                     indent_size: Some(4),
+                    ..Default::default()
                 },
             )
             .unwrap();
@@ -1015,6 +2768,355 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_write_default_generator() {
+        let config = WriteConfig {
+            write_default_generator: true,
+            ..Default::default()
+        };
+        let xml = Feed::default()
+            .write_with_config(Vec::new(), config)
+            .unwrap();
+        let feed = Feed::read_from(xml.as_slice()).unwrap();
+        let generator = feed.generator().unwrap();
+        assert_eq!(generator.value(), "atom_syndication");
+        assert_eq!(generator.uri(), Some("https://docs.rs/atom_syndication"));
+        assert_eq!(generator.version(), Some(env!("CARGO_PKG_VERSION")));
+
+        let mut feed = Feed::default();
+        feed.set_generator(Generator {
+            value: "Feed Generator".to_string(),
+            ..Default::default()
+        });
+        let xml = feed.write_with_config(Vec::new(), config).unwrap();
+        assert_eq!(Feed::read_from(xml.as_slice()).unwrap(), feed);
+
+        let xml = Feed::default().write_to(Vec::new()).unwrap();
+        assert!(Feed::read_from(xml.as_slice())
+            .unwrap()
+            .generator()
+            .is_none());
+    }
+
+    #[test]
+    fn test_write_omit_alternate_rel() {
+        let alternate = Link {
+            href: "http://example.com/".to_string(),
+            ..Default::default()
+        };
+        let related = Link {
+            href: "http://example.com/related".to_string(),
+            rel: "related".to_string(),
+            ..Default::default()
+        };
+        let feed = Feed {
+            links: vec![alternate.clone(), related.clone()],
+            entries: vec![Entry {
+                links: vec![alternate],
+                source: Some(crate::source::Source {
+                    links: vec![related],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let config = WriteConfig {
+            write_document_declaration: false,
+            omit_alternate_rel: true,
+            ..Default::default()
+        };
+        let xml = feed.write_with_config(Vec::new(), config).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+        assert_eq!(
+            xml.matches(r#"<link href="http://example.com/"/>"#).count(),
+            2
+        );
+        assert!(xml.contains(r#"<link href="http://example.com/related" rel="related"/>"#));
+        assert_eq!(Feed::read_from(xml.as_bytes()).unwrap(), feed);
+
+        let xml = feed.write_to(Vec::new()).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+        assert_eq!(xml.matches(r#"rel="alternate""#).count(), 2);
+    }
+
+    #[test]
+    fn test_write_buffered() {
+        struct CallCounter {
+            out: Vec<u8>,
+            writes: usize,
+        }
+
+        impl Write for CallCounter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.writes += 1;
+                self.out.write(buf)
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let feed = Feed {
+            entries: vec![Entry::default(); 10],
+            ..Default::default()
+        };
+        let counter = CallCounter {
+            out: Vec::new(),
+            writes: 0,
+        };
+        let counter = feed.write_to(counter).unwrap();
+        assert_eq!(counter.writes, 1);
+        assert_eq!(counter.out, feed.write_to(Vec::new()).unwrap());
+
+        let counter = CallCounter {
+            out: Vec::new(),
+            writes: 0,
+        };
+        let counter = feed
+            .write_with_config(counter, WriteConfig::default())
+            .unwrap();
+        assert!(counter.writes > 1);
+
+        let config = WriteConfig {
+            line_ending: LineEnding::CrLf,
+            indent_size: Some(2),
+            ..Default::default()
+        };
+        let out = feed.write_with_config(Vec::new(), config).unwrap();
+        assert!(String::from_utf8(out).unwrap().contains("\r\n  <entry>"));
+    }
+
+    #[test]
+    fn test_write_rfc_element_order() {
+        fn children(xml: &[u8], parent: &str) -> Vec<String> {
+            let mut reader = Reader::from_reader(xml);
+            let mut names = Vec::new();
+            let mut path = Vec::new();
+            loop {
+                let event = reader.read_event().unwrap();
+                let empty = matches!(event, Event::Empty(_));
+                match event {
+                    Event::Start(element) | Event::Empty(element) => {
+                        let name = str::from_utf8(element.name().as_ref()).unwrap().to_string();
+                        if path.last().map(String::as_str) == Some(parent) {
+                            names.push(name.clone());
+                        }
+                        if !empty {
+                            path.push(name);
+                        }
+                    }
+                    Event::End(_) => {
+                        path.pop();
+                    }
+                    Event::Eof => return names,
+                    _ => {}
+                }
+            }
+        }
+
+        let text = Text::plain("text");
+        let person = Person {
+            name: "name".to_string(),
+            ..Default::default()
+        };
+        let mut extensions = ExtensionMap::new();
+        extensions.entry("ext".to_string()).or_default().insert(
+            "a".to_string(),
+            vec![crate::extension::Extension {
+                name: "ext:a".to_string(),
+                ..Default::default()
+            }],
+        );
+        let entry = Entry {
+            authors: vec![person.clone()],
+            categories: vec![Category::default()],
+            contributors: vec![person.clone()],
+            links: vec![Link::default()],
+            published: Some(default_fixed_datetime()),
+            rights: Some(text.clone()),
+            source: Some(crate::source::Source {
+                authors: vec![person.clone()],
+                ..Default::default()
+            }),
+            summary: Some(text.clone()),
+            content: Some(crate::content::Content::default()),
+            extensions: extensions.clone(),
+            ..Default::default()
+        };
+        let feed = Feed {
+            authors: vec![person.clone()],
+            categories: vec![Category::default()],
+            contributors: vec![person],
+            generator: Some(Generator::default()),
+            icon: Some("icon".to_string()),
+            links: vec![Link::default()],
+            logo: Some("logo".to_string()),
+            rights: Some(text.clone()),
+            subtitle: Some(text),
+            extensions,
+            entries: vec![entry],
+            ..Default::default()
+        };
+
+        let config = WriteConfig {
+            rfc_element_order: true,
+            ..Default::default()
+        };
+        let xml = feed.write_with_config(Vec::new(), config).unwrap();
+        assert_eq!(
+            children(&xml, "feed"),
+            vec![
+                "author",
+                "category",
+                "contributor",
+                "generator",
+                "icon",
+                "id",
+                "link",
+                "logo",
+                "rights",
+                "subtitle",
+                "title",
+                "updated",
+                "ext:a",
+                "entry"
+            ]
+        );
+        assert_eq!(
+            children(&xml, "entry"),
+            vec![
+                "author",
+                "category",
+                "content",
+                "contributor",
+                "id",
+                "link",
+                "published",
+                "rights",
+                "source",
+                "summary",
+                "title",
+                "updated",
+                "ext:a"
+            ]
+        );
+        assert_eq!(
+            children(&xml, "source"),
+            vec!["author", "id", "title", "updated"]
+        );
+        assert_eq!(Feed::read_from(xml.as_slice()).unwrap(), feed);
+
+        let xml = feed.write_to(Vec::new()).unwrap();
+        assert_eq!(
+            children(&xml, "entry"),
+            vec![
+                "title",
+                "id",
+                "updated",
+                "author",
+                "category",
+                "contributor",
+                "link",
+                "published",
+                "rights",
+                "source",
+                "summary",
+                "content",
+                "ext:a"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_subseconds() {
+        let xml = r#"<feed><updated>2017-06-03T15:15:44.123Z</updated><entry>
+            <updated>2017-06-03T15:15:44Z</updated>
+            <published>2017-06-01T15:15:44.5+02:00</published>
+        </entry></feed>"#;
+        let feed = Feed::read_from(xml.as_bytes()).unwrap();
+        let config = WriteConfig {
+            write_document_declaration: false,
+            ..Default::default()
+        };
+
+        let xml = feed.write_with_config(Vec::new(), config).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+        assert!(xml.contains("<updated>2017-06-03T15:15:44+00:00</updated>"));
+        assert!(xml.contains("<published>2017-06-01T15:15:44+02:00</published>"));
+        assert!(!xml.contains("44."));
+
+        let config = WriteConfig {
+            preserve_subseconds: true,
+            ..config
+        };
+        let xml = feed.write_with_config(Vec::new(), config).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+        assert!(xml.contains("<updated>2017-06-03T15:15:44.123+00:00</updated>"));
+        assert!(xml.contains("<published>2017-06-01T15:15:44.500+02:00</published>"));
+        assert_eq!(Feed::read_from(xml.as_bytes()).unwrap(), feed);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_read_gzip() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+
+        let feed = Feed {
+            title: "Feed Title".into(),
+            ..Default::default()
+        };
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        feed.write_to(&mut encoder).unwrap();
+        let compressed = encoder.finish().unwrap();
+        assert_eq!(Feed::read_gzip(compressed.as_slice()).unwrap(), feed);
+        assert!(Feed::read_gzip(b"<feed></feed>".as_ref()).is_err());
+    }
+
+    #[test]
+    fn test_write_crlf() {
+        let feed = Feed {
+            title: Text::plain("Line\nbreak\r\nagain"),
+            ..Default::default()
+        };
+        let xml = feed
+            .write_with_config(
+                Vec::new(),
+                WriteConfig {
+                    indent_size: Some(2),
+                    line_ending: LineEnding::CrLf,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&xml),
+            "<?xml version=\"1.0\"?>\r\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\r\n  <title>Line\nbreak\r\nagain</title>\r\n  <id></id>\r\n  <updated>1970-01-01T00:00:00+00:00</updated>\r\n</feed>"
+        );
+        let loaded_feed = Feed::read_from(xml.as_slice()).unwrap();
+        assert_eq!(loaded_feed, feed);
+    }
+
+    #[test]
+    fn test_display() {
+        let feed = Feed {
+            title: Text::plain("Caf\u{e9} \u{1f600}"),
+            ..Default::default()
+        };
+        let xml = feed.write_to(Vec::new()).unwrap();
+        assert_eq!(format!("{}", feed), String::from_utf8(xml).unwrap());
+
+        let mut s = String::new();
+        let mut writer = FmtWriter::new(&mut s);
+        let bytes = "\u{1f600}".as_bytes();
+        writer.write_all(&bytes[..1]).unwrap();
+        writer.write_all(&bytes[1..]).unwrap();
+        assert!(writer.write_all(b"\xff").is_err());
+        assert_eq!(s, "\u{1f600}");
+    }
+
     #[test]
     fn test_write_no_decl_indented() {
         let feed = Feed::default();
@@ -1024,6 +3126,7 @@ mod test {
                 WriteConfig {
                     write_document_declaration: false,
                     indent_size: Some(4),
+                    ..Default::default()
                 },
             )
             .unwrap();
@@ -1,10 +1,48 @@
 use std::io::BufRead;
 
 use quick_xml::events::attributes::Attributes;
+use quick_xml::events::BytesStart;
 use quick_xml::Reader;
 
 use crate::error::Error;
+use crate::feed::ParseOpts;
 
-pub(crate) trait FromXml: Sized {
-    fn from_xml<R: BufRead>(reader: &mut Reader<R>, atts: Attributes<'_>) -> Result<Self, Error>;
+/// A type which can be read from an XML element.
+pub trait FromXml: Sized {
+    /// Reads the value from the element whose start tag has just been read from `reader`.
+    ///
+    /// `atts` are the attributes of that start tag. The implementation must consume the events
+    /// up to and including the matching end tag, so that reading can continue after the element.
+    /// Text should be unescaped using the entities of `opts`. This is not called for empty
+    /// elements like `<title/>`, which have no end tag.
+    fn from_xml<R: BufRead>(
+        reader: &mut Reader<R>,
+        atts: Attributes<'_>,
+        opts: &ParseOpts,
+    ) -> Result<Self, Error>;
+}
+
+/// A type which can also be read from an empty element, e.g. `<title/>`.
+pub(crate) trait FromEmptyXml: FromXml {
+    /// Reads the value from an empty element, whose only content is `atts`.
+    fn from_empty_xml<R: BufRead>(
+        reader: &mut Reader<R>,
+        atts: Attributes<'_>,
+        opts: &ParseOpts,
+    ) -> Result<Self, Error>;
+
+    /// Reads the value from `element`, which was read from an `Event::Empty` if `empty` is set
+    /// and from an `Event::Start` otherwise.
+    fn from_element<R: BufRead>(
+        reader: &mut Reader<R>,
+        element: &BytesStart<'_>,
+        empty: bool,
+        opts: &ParseOpts,
+    ) -> Result<Self, Error> {
+        if empty {
+            Self::from_empty_xml(reader, element.attributes(), opts)
+        } else {
+            Self::from_xml(reader, element.attributes(), opts)
+        }
+    }
 }
@@ -1,15 +1,27 @@
-use std::borrow::Cow;
-use std::io::{BufRead, Write};
+use alloc::string::{String, ToString};
+use core::fmt;
 
-use quick_xml::events::attributes::Attributes;
-use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
-use quick_xml::Reader;
-use quick_xml::Writer;
+#[cfg(feature = "std")]
+use std::{
+    borrow::Cow,
+    io::{BufRead, Write},
+};
 
-use crate::error::{Error, XmlError};
-use crate::fromxml::FromXml;
-use crate::toxml::ToXml;
-use crate::util::{atom_text, attr_value, decode};
+#[cfg(feature = "std")]
+use quick_xml::{
+    events::{attributes::Attributes, BytesEnd, BytesStart, BytesText, Event},
+    Reader, Writer,
+};
+
+use crate::util::resolve_uri;
+#[cfg(feature = "std")]
+use crate::{
+    error::{Error, XmlError},
+    feed::ParseOpts,
+    fromxml::{FromEmptyXml, FromXml},
+    toxml::ToXml,
+    util::{atom_text, attr_value, decode},
+};
 
 /// Represents the generator of an Atom feed
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
@@ -81,6 +93,48 @@ impl Generator {
         self.uri.as_deref()
     }
 
+    /// Return the URI of the generator parsed as a `Url`.
+    ///
+    /// Returns `None` if it is not set. The stored string is left untouched if it fails to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Generator;
+    ///
+    /// let mut generator = Generator::default();
+    /// generator.set_uri("http://example.com/generator".to_string());
+    /// assert_eq!(generator.uri_url().unwrap().unwrap().as_str(), "http://example.com/generator");
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn uri_url(&self) -> Option<Result<url::Url, url::ParseError>> {
+        self.uri.as_deref().map(url::Url::parse)
+    }
+
+    /// Return the URI of the generator resolved against the base URL in scope.
+    ///
+    /// Returns `None` if it is not set. See `Link::resolved_href` for how it is resolved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Generator;
+    ///
+    /// let mut generator = Generator::default();
+    /// generator.set_uri("/generator".to_string());
+    /// assert_eq!(
+    ///     generator.resolved_uri(Some("http://example.com/feed.xml")),
+    ///     Some("http://example.com/generator".to_string())
+    /// );
+    /// ```
+    pub fn resolved_uri(&self, base: Option<&str>) -> Option<String> {
+        let uri = self.uri.as_deref()?;
+        Some(match base {
+            Some(base) => resolve_uri(base, uri),
+            None => uri.to_string(),
+        })
+    }
+
     /// Set the URI for the generator.
     ///
     /// # Examples
@@ -131,31 +185,86 @@ impl Generator {
     }
 }
 
+impl fmt::Display for Generator {
+    /// Formats the generator as its name followed by the version in parentheses and the URI in
+    /// angle brackets, leaving out the ones which are not set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Generator;
+    ///
+    /// let generator = Generator {
+    ///     value: "Example Toolkit".to_string(),
+    ///     uri: Some("http://example.com/toolkit".to_string()),
+    ///     version: Some("1.0".to_string()),
+    /// };
+    /// assert_eq!(generator.to_string(), "Example Toolkit (1.0) <http://example.com/toolkit>");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)?;
+        if let Some(ref version) = self.version {
+            write!(f, " ({})", version)?;
+        }
+        if let Some(ref uri) = self.uri {
+            write!(f, " <{}>", uri)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Generator {
+    /// Returns the generator identifying this crate.
+    pub(crate) fn atom_syndication() -> Self {
+        Generator {
+            value: "atom_syndication".to_string(),
+            uri: Some("https://docs.rs/atom_syndication".to_string()),
+            version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
 impl FromXml for Generator {
     fn from_xml<B: BufRead>(
+        reader: &mut Reader<B>,
+        atts: Attributes<'_>,
+        opts: &ParseOpts,
+    ) -> Result<Self, Error> {
+        let mut generator = Generator::from_empty_xml(reader, atts, opts)?;
+        generator.value = atom_text(reader, opts)?.unwrap_or_default();
+
+        Ok(generator)
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromEmptyXml for Generator {
+    fn from_empty_xml<B: BufRead>(
         reader: &mut Reader<B>,
         mut atts: Attributes<'_>,
+        opts: &ParseOpts,
     ) -> Result<Self, Error> {
         let mut generator = Generator::default();
 
         for att in atts.with_checks(false).flatten() {
             match decode(att.key.as_ref(), reader)? {
                 Cow::Borrowed("uri") => {
-                    generator.uri = Some(attr_value(&att, reader)?.to_string());
+                    generator.uri = Some(attr_value(&att, reader, opts)?.into_owned());
                 }
                 Cow::Borrowed("version") => {
-                    generator.version = Some(attr_value(&att, reader)?.to_string());
+                    generator.version = Some(attr_value(&att, reader, opts)?.into_owned());
                 }
                 _ => {}
             }
         }
 
-        generator.value = atom_text(reader)?.unwrap_or_default();
-
         Ok(generator)
     }
 }
 
+#[cfg(feature = "std")]
 impl ToXml for Generator {
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
         let name = "generator";
@@ -169,6 +278,13 @@ impl ToXml for Generator {
             element.push_attribute(("version", &**version));
         }
 
+        if self.value.is_empty() {
+            writer
+                .write_event(Event::Empty(element))
+                .map_err(XmlError::new)?;
+            return Ok(());
+        }
+
         writer
             .write_event(Event::Start(element))
             .map_err(XmlError::new)?;
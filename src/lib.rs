@@ -1,3 +1,4 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs, rust_2018_idioms)]
 #![doc(html_root_url = "https://docs.rs/atom_syndication/")]
 
@@ -9,6 +10,7 @@
 //! the `FromStr` trait.
 //!
 //! ```no_run
+//! # #[cfg(feature = "std")] {
 //! use std::fs::File;
 //! use std::io::BufReader;
 //! use atom_syndication::Feed;
@@ -18,6 +20,7 @@
 //!
 //! let string = "<feed></feed>";
 //! let feed = string.parse::<Feed>().unwrap();
+//! # }
 //! ```
 //!
 //! # Writing
@@ -28,6 +31,7 @@
 //! ## Example
 //!
 //! ```no_run
+//! # #[cfg(feature = "std")] {
 //! use std::fs::File;
 //! use std::io::{BufReader, sink};
 //! use atom_syndication::Feed;
@@ -40,7 +44,34 @@
 //!
 //! // convert the feed to a string
 //! let string = feed.to_string();
+//! # }
 //! ```
+//!
+//! Attributes are written in a fixed order: namespace declarations first, then `xml:base`,
+//! `xml:lang` and `xml:id`, then the attributes of the element in the order they are defined in
+//! RFC 4287, e.g. `href`, `rel`, `type`, `hreflang`, `title` and `length` for links. Attributes
+//! of extension elements are written sorted by name.
+//!
+//! Elements are written with the title, id and updated date first. The extension elements of a
+//! feed follow its other metadata and come before its entries, as validators expect. Set
+//! `WriteConfig::rfc_element_order` to write the elements in the order of the schema in RFC 4287
+//! instead.
+//!
+//! Dates are written in whole seconds with a numeric offset, e.g. `2017-06-03T15:15:44+00:00`,
+//! unless `WriteConfig::preserve_subseconds` is set.
+//!
+//! Values are escaped when written, so `&`, `<`, `>`, `"` and `'` in text constructs of type
+//! `text` or `html`, content, people, generators, extensions and attributes are always written as
+//! well-formed XML. The only exception is the value of `xhtml` text constructs and content, which
+//! is markup and written as it is.
+//!
+//! # `no_std`
+//!
+//! Reading and writing depend on `std::io` and are enabled by the default `std` feature. Without
+//! it the crate is `#![no_std]` and only requires `alloc`, which is enough to build and
+//! manipulate the feed types in memory. The `builders` feature requires `std`.
+
+extern crate alloc;
 
 #[cfg(feature = "serde")]
 #[macro_use]
@@ -50,6 +81,11 @@ extern crate serde;
 #[macro_use]
 extern crate derive_builder;
 
+#[cfg(feature = "builders")]
+#[macro_use]
+mod macros;
+
+mod canonical;
 mod category;
 mod content;
 mod entry;
@@ -60,34 +96,54 @@ mod person;
 mod source;
 mod text;
 
+#[cfg(feature = "std")]
+mod check;
+#[cfg(feature = "std")]
 mod error;
+#[cfg(feature = "std")]
 mod fromxml;
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(feature = "std")]
+mod parser;
+#[cfg(feature = "std")]
 mod toxml;
 mod util;
 
 /// Types and functions for namespaced extensions.
 pub mod extension;
 
+#[cfg(feature = "std")]
+pub mod traits;
+
+/// Helpers for testing code which produces Atom feeds.
+#[cfg(feature = "testing")]
+pub mod test_util;
+
 pub use crate::category::Category;
 #[cfg(feature = "builders")]
 pub use crate::category::CategoryBuilder;
-pub use crate::content::Content;
 #[cfg(feature = "builders")]
 pub use crate::content::ContentBuilder;
-pub use crate::entry::Entry;
+pub use crate::content::{Content, ContentBody};
 #[cfg(feature = "builders")]
 pub use crate::entry::EntryBuilder;
-pub use crate::error::Error;
-pub use crate::feed::Feed;
+pub use crate::entry::{DisplayBody, Entry};
+#[cfg(feature = "std")]
+pub use crate::error::{Error, XmlError};
 #[cfg(feature = "builders")]
 pub use crate::feed::FeedBuilder;
-pub use crate::feed::WriteConfig;
+pub use crate::feed::{Feed, FeedDiff, PrologItem};
+#[cfg(feature = "std")]
+pub use crate::feed::{LineEnding, MissingDate, MissingHref, MissingId, ParseOpts, WriteConfig};
 pub use crate::generator::Generator;
 #[cfg(feature = "builders")]
 pub use crate::generator::GeneratorBuilder;
-pub use crate::link::Link;
 #[cfg(feature = "builders")]
 pub use crate::link::LinkBuilder;
+pub use crate::link::{LengthError, Link, LinkRel};
+#[cfg(feature = "std")]
+pub use crate::parser::FeedParser;
 pub use crate::person::Person;
 #[cfg(feature = "builders")]
 pub use crate::person::PersonBuilder;
@@ -98,3 +154,6 @@ pub use crate::source::SourceBuilder;
 pub use crate::text::TextBuilder;
 pub use crate::text::{Text, TextType};
 pub use crate::util::FixedDateTime;
+
+/// The XML namespace of Atom elements, as defined by RFC 4287.
+pub const ATOM_NS: &str = "http://www.w3.org/2005/Atom";
@@ -1,17 +1,30 @@
-use std::borrow::Cow;
-use std::io::{BufRead, Write};
+use alloc::string::{String, ToString};
+use core::fmt;
 
-use quick_xml::events::{BytesStart, Event};
-use quick_xml::Reader;
-use quick_xml::Writer;
+#[cfg(feature = "std")]
+use std::{
+    borrow::Cow,
+    io::{BufRead, Write},
+};
 
-use crate::error::{Error, XmlError};
-use crate::toxml::ToXml;
-use crate::util::{attr_value, decode};
+#[cfg(feature = "std")]
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader, Writer,
+};
+
+use crate::util::{join_base, resolve_uri};
+#[cfg(feature = "std")]
+use crate::{
+    error::{Error, XmlError},
+    feed::{MissingHref, ParseOpts, WriteConfig},
+    toxml::ToXml,
+    util::{attr_value, decode},
+};
 
 /// Represents a link in an Atom feed
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "builders", derive(Builder))]
 #[cfg_attr(
     feature = "builders",
@@ -34,6 +47,27 @@ pub struct Link {
     pub title: Option<String>,
     /// The length of the resource, in bytes.
     pub length: Option<String>,
+    /// Base URL for resolving any relative references found in the element.
+    pub base: Option<String>,
+    /// The base URL in scope of the link when it was read, which combines the `xml:base` of
+    /// the feed, the entry and the link itself.
+    ///
+    /// It is derived from the document, so it is neither written, serialized nor compared.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "builders", builder(setter(skip)))]
+    pub effective_base: Option<String>,
+}
+
+impl PartialEq for Link {
+    fn eq(&self, other: &Self) -> bool {
+        self.href == other.href
+            && self.rel == other.rel
+            && self.hreflang == other.hreflang
+            && self.mime_type == other.mime_type
+            && self.title == other.title
+            && self.length == other.length
+            && self.base == other.base
+    }
 }
 
 impl Default for Link {
@@ -45,6 +79,8 @@ impl Default for Link {
             mime_type: Default::default(),
             title: Default::default(),
             length: Default::default(),
+            base: Default::default(),
+            effective_base: Default::default(),
         }
     }
 }
@@ -65,6 +101,60 @@ impl Link {
         self.href.as_str()
     }
 
+    /// Return the URI of the referenced resource parsed as a `Url`.
+    ///
+    /// The stored string is left untouched if it fails to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_href("http://example.com/feed");
+    /// assert_eq!(link.href_url().unwrap().as_str(), "http://example.com/feed");
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn href_url(&self) -> Result<url::Url, url::ParseError> {
+        url::Url::parse(&self.href)
+    }
+
+    /// Return the URI of the referenced resource resolved against the base URL in scope.
+    ///
+    /// The `xml:base` of the link itself is applied on top of `base`, which is the base URL
+    /// in scope of the link, e.g. the one of the containing feed. Without `base` the
+    /// `effective_base` recorded when reading the link is used, so a link read from a feed is
+    /// resolved against the `xml:base` of the feed and entry it appears in. Resolution follows
+    /// RFC 3986, so references with a scheme such as `mailto:` are kept and protocol-relative
+    /// ones take the scheme of the base. Without any base the URI is returned as it is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_href("../audio.mp3");
+    /// assert_eq!(link.resolved_href(Some("http://example.com/podcast/feed.xml")), "http://example.com/audio.mp3");
+    ///
+    /// link.set_href("//cdn.example.com/audio.mp3");
+    /// assert_eq!(link.resolved_href(Some("https://example.com/")), "https://cdn.example.com/audio.mp3");
+    ///
+    /// link.set_base("http://example.com/episodes/".to_string());
+    /// link.set_href("1.mp3");
+    /// assert_eq!(link.resolved_href(None), "http://example.com/episodes/1.mp3");
+    /// ```
+    pub fn resolved_href(&self, base: Option<&str>) -> String {
+        let base = match base {
+            Some(base) => join_base(Some(base), self.base.as_deref()),
+            None => self.effective_base.clone().or_else(|| self.base.clone()),
+        };
+        match base {
+            Some(base) => resolve_uri(&base, &self.href),
+            None => self.href.clone(),
+        }
+    }
+
     /// Set the URI of the referenced resource.
     ///
     /// # Examples
@@ -114,6 +204,27 @@ impl Link {
         self.rel = rel.into()
     }
 
+    /// Return the relation type of this link as a `LinkRel`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Link, LinkRel};
+    ///
+    /// let mut link = Link::default();
+    /// assert_eq!(link.relation(), LinkRel::Alternate);
+    ///
+    /// link.set_rel(LinkRel::License);
+    /// assert_eq!(link.rel(), "license");
+    /// assert_eq!(link.relation(), LinkRel::License);
+    ///
+    /// link.set_rel("http://www.iana.org/assignments/relation/edit");
+    /// assert_eq!(link.relation(), LinkRel::Edit);
+    /// ```
+    pub fn relation(&self) -> LinkRel {
+        LinkRel::from(self.rel.as_str())
+    }
+
     /// Return the language of the referenced resource.
     ///
     /// # Examples
@@ -241,51 +352,294 @@ impl Link {
     {
         self.length = length.into()
     }
+
+    /// Return the content length of the referenced resource parsed as a number of bytes.
+    ///
+    /// Returns `None` if the length is not set. A negative length is reported as
+    /// `LengthError::Negative`, any other value which is not a `u64` as `LengthError::Invalid`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{LengthError, Link};
+    ///
+    /// let mut link = Link::default();
+    /// assert_eq!(link.length_bytes(), None);
+    ///
+    /// link.set_length("1000".to_string());
+    /// assert_eq!(link.length_bytes(), Some(Ok(1000)));
+    ///
+    /// link.set_length("-1".to_string());
+    /// assert_eq!(link.length_bytes(), Some(Err(LengthError::Negative)));
+    ///
+    /// link.set_length("1 MB".to_string());
+    /// assert_eq!(link.length_bytes(), Some(Err(LengthError::Invalid)));
+    /// ```
+    pub fn length_bytes(&self) -> Option<Result<u64, LengthError>> {
+        let length = self.length.as_deref()?.trim();
+        let negative = match length.strip_prefix('-') {
+            Some(digits) => {
+                digits.bytes().all(|b| b.is_ascii_digit()) && digits.bytes().any(|b| b != b'0')
+            }
+            None => false,
+        };
+        Some(if negative {
+            Err(LengthError::Negative)
+        } else {
+            length.parse().map_err(|_| LengthError::Invalid)
+        })
+    }
+
+    /// Return base URL of the link.
+    pub fn base(&self) -> Option<&str> {
+        self.base.as_deref()
+    }
+
+    /// Set base URL of the link.
+    pub fn set_base<V>(&mut self, base: V)
+    where
+        V: Into<Option<String>>,
+    {
+        self.base = base.into();
+    }
+
+    /// Records the effective base URL of this link, given the base URL `parent` in scope of it.
+    #[cfg(feature = "std")]
+    pub(crate) fn apply_base(&mut self, parent: Option<&str>) {
+        self.effective_base = join_base(parent, self.base.as_deref());
+    }
+}
+
+impl fmt::Display for Link {
+    /// Formats the link as its `rel`, a colon and its `href`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Link;
+    ///
+    /// let mut link = Link::default();
+    /// link.set_href("http://example.com/feed.xml");
+    /// assert_eq!(link.to_string(), "alternate: http://example.com/feed.xml");
+    ///
+    /// link.set_rel("self");
+    /// assert_eq!(link.to_string(), "self: http://example.com/feed.xml");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.rel, self.href)
+    }
+}
+
+/// The reason the length of a link is not a number of bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthError {
+    /// The length is negative.
+    Negative,
+    /// The length is not an integer or is too large.
+    Invalid,
+}
+
+impl fmt::Display for LengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LengthError::Negative => f.write_str("length must not be negative"),
+            LengthError::Invalid => f.write_str("length is not a valid number of bytes"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthError {}
+
+/// The prefix of IANA link relations written as IRIs, such as
+/// `http://www.iana.org/assignments/relation/license`.
+const IANA_RELATION_PREFIX: &str = "http://www.iana.org/assignments/relation/";
+
+/// The relation type of a link, as returned by [`Link::relation`].
+///
+/// Registered relations are matched both by name and by their full IANA IRI. Other relations,
+/// which RFC 4287 requires to be IRIs, are kept as `Extension`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LinkRel {
+    /// `alternate`: an alternate version of the resource, the default relation.
+    Alternate,
+    /// `related`: a related resource.
+    Related,
+    /// `self`: the resource itself, e.g. the URL of the feed.
+    SelfLink,
+    /// `enclosure`: a potentially large related resource, e.g. an audio file.
+    Enclosure,
+    /// `via`: the source of the information in the resource.
+    Via,
+    /// `license`: the license of the resource, as defined by RFC 4946.
+    License,
+    /// `payment`: where payment for the resource is accepted.
+    Payment,
+    /// `search`: a resource which can be used to search the feed.
+    Search,
+    /// `replies`: responses to the resource, as defined by RFC 4685.
+    Replies,
+    /// `edit`: where the resource can be edited, as defined by RFC 5023.
+    Edit,
+    /// `edit-media`: where the media of the resource can be edited, as defined by RFC 5023.
+    EditMedia,
+    /// Any other relation.
+    Extension(String),
+}
+
+impl LinkRel {
+    /// Return the relation as written in the `rel` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::LinkRel;
+    ///
+    /// assert_eq!(LinkRel::EditMedia.as_str(), "edit-media");
+    /// assert_eq!(LinkRel::Extension("http://example.com/rel".to_string()).as_str(), "http://example.com/rel");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        match self {
+            LinkRel::Alternate => "alternate",
+            LinkRel::Related => "related",
+            LinkRel::SelfLink => "self",
+            LinkRel::Enclosure => "enclosure",
+            LinkRel::Via => "via",
+            LinkRel::License => "license",
+            LinkRel::Payment => "payment",
+            LinkRel::Search => "search",
+            LinkRel::Replies => "replies",
+            LinkRel::Edit => "edit",
+            LinkRel::EditMedia => "edit-media",
+            LinkRel::Extension(rel) => rel,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for LinkRel {
+    fn from(rel: &'a str) -> Self {
+        match rel.strip_prefix(IANA_RELATION_PREFIX).unwrap_or(rel) {
+            "alternate" => LinkRel::Alternate,
+            "related" => LinkRel::Related,
+            "self" => LinkRel::SelfLink,
+            "enclosure" => LinkRel::Enclosure,
+            "via" => LinkRel::Via,
+            "license" => LinkRel::License,
+            "payment" => LinkRel::Payment,
+            "search" => LinkRel::Search,
+            "replies" => LinkRel::Replies,
+            "edit" => LinkRel::Edit,
+            "edit-media" => LinkRel::EditMedia,
+            _ => LinkRel::Extension(rel.to_string()),
+        }
+    }
+}
+
+impl From<LinkRel> for String {
+    fn from(rel: LinkRel) -> Self {
+        match rel {
+            LinkRel::Extension(rel) => rel,
+            rel => rel.as_str().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for LinkRel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
+#[cfg(feature = "std")]
 impl Link {
+    /// Reads a link from the attributes of `element`. Returns `None` if the link has no `href`
+    /// and is left out according to `ParseOpts::missing_href`.
     pub(crate) fn from_xml<'s, B: BufRead>(
         reader: &mut Reader<B>,
         element: &'s BytesStart<'s>,
-    ) -> Result<Self, Error> {
+        opts: &ParseOpts,
+    ) -> Result<Option<Self>, Error> {
         let mut link = Link::default();
+        let mut has_href = false;
 
         for att in element.attributes().with_checks(false).flatten() {
             match decode(att.key.as_ref(), reader)? {
-                Cow::Borrowed("href") => link.href = attr_value(&att, reader)?.to_string(),
-                Cow::Borrowed("rel") => link.rel = attr_value(&att, reader)?.to_string(),
+                Cow::Borrowed("href") => {
+                    link.href = attr_value(&att, reader, opts)?.into_owned();
+                    has_href = true;
+                }
+                Cow::Borrowed("rel") => link.rel = attr_value(&att, reader, opts)?.into_owned(),
                 Cow::Borrowed("hreflang") => {
-                    link.hreflang = Some(attr_value(&att, reader)?.to_string())
+                    link.hreflang = Some(attr_value(&att, reader, opts)?.into_owned())
                 }
                 Cow::Borrowed("type") => {
-                    link.mime_type = Some(attr_value(&att, reader)?.to_string())
+                    link.mime_type = Some(attr_value(&att, reader, opts)?.into_owned())
+                }
+                Cow::Borrowed("title") => {
+                    link.title = Some(attr_value(&att, reader, opts)?.into_owned())
                 }
-                Cow::Borrowed("title") => link.title = Some(attr_value(&att, reader)?.to_string()),
                 Cow::Borrowed("length") => {
-                    link.length = Some(attr_value(&att, reader)?.to_string())
+                    link.length = Some(attr_value(&att, reader, opts)?.into_owned())
+                }
+                Cow::Borrowed("xml:base") => {
+                    link.base = Some(attr_value(&att, reader, opts)?.into_owned())
                 }
                 _ => {}
             }
         }
 
-        Ok(link)
+        if !has_href {
+            match opts.missing_href {
+                MissingHref::Empty => {}
+                MissingHref::Skip => return Ok(None),
+                MissingHref::Error => {
+                    return Err(Error::MissingAttribute {
+                        element: "link",
+                        attribute: "href",
+                    })
+                }
+            }
+        }
+
+        Ok(Some(link))
     }
 }
 
+#[cfg(feature = "std")]
 impl ToXml for Link {
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        self.to_xml_with_config(writer, &WriteConfig::default())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Link {
+    /// Writes this link according to the options of `config`.
+    pub(crate) fn to_xml_with_config<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        config: &WriteConfig,
+    ) -> Result<(), XmlError> {
         let mut element = BytesStart::new("link");
+
+        if let Some(ref base) = self.base {
+            element.push_attribute(("xml:base", base.as_str()));
+        }
+
         element.push_attribute(("href", &*self.href));
-        element.push_attribute(("rel", &*self.rel));
 
-        if let Some(ref hreflang) = self.hreflang {
-            element.push_attribute(("hreflang", &**hreflang));
+        if !(config.omit_alternate_rel && self.rel == "alternate") {
+            element.push_attribute(("rel", &*self.rel));
         }
 
         if let Some(ref mime_type) = self.mime_type {
             element.push_attribute(("type", &**mime_type));
         }
 
+        if let Some(ref hreflang) = self.hreflang {
+            element.push_attribute(("hreflang", &**hreflang));
+        }
+
         if let Some(ref title) = self.title {
             element.push_attribute(("title", &**title));
         }
@@ -308,4 +662,19 @@ impl LinkBuilder {
     pub fn build(&self) -> Link {
         self.build_impl().unwrap()
     }
+
+    /// Sets the content length of the referenced resource in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::LinkBuilder;
+    ///
+    /// let link = LinkBuilder::default().length_bytes(1000).build();
+    /// assert_eq!(link.length(), Some("1000"));
+    /// ```
+    pub fn length_bytes(&mut self, length: u64) -> &mut Self {
+        self.length = Some(Some(length.to_string()));
+        self
+    }
 }
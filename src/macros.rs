@@ -0,0 +1,42 @@
+/// Implements the `clear_*` methods of a builder, which remove the items of its lists set so
+/// far.
+///
+/// Each method is given as `clear_method: field, setter(Item)`, where `setter` appends a single
+/// `Item` to the list in `field`.
+macro_rules! clear_methods {
+    (
+        $builder:ident => $built:ident {
+            $($clear:ident: $field:ident, $each:ident($item:ident);)*
+        }
+    ) => {
+        $(
+            #[doc = concat!("Removes the ", stringify!($field), " set so far.")]
+            #[doc = ""]
+            #[doc = concat!(
+                "The `", stringify!($field), "` setter replaces the ", stringify!($field),
+                " as a whole, while `", stringify!($each), "` appends to them."
+            )]
+            #[doc = ""]
+            #[doc = "# Examples"]
+            #[doc = ""]
+            #[doc = "```"]
+            #[doc = concat!(
+                "use atom_syndication::{", stringify!($builder), ", ", stringify!($item), "};"
+            )]
+            #[doc = ""]
+            #[doc = concat!("let mut builder = ", stringify!($builder), "::default();")]
+            #[doc = concat!("builder.", stringify!($each), "(", stringify!($item), "::default());")]
+            #[doc = concat!(
+                "let ", stringify!($built), " = builder.", stringify!($clear), "().build();"
+            )]
+            #[doc = concat!(
+                "assert!(", stringify!($built), ".", stringify!($field), "().is_empty());"
+            )]
+            #[doc = "```"]
+            pub fn $clear(&mut self) -> &mut Self {
+                self.$field = Some(Vec::new());
+                self
+            }
+        )*
+    };
+}
@@ -0,0 +1,105 @@
+use std::io::BufRead;
+use std::ops::Range;
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use rayon::prelude::*;
+
+use crate::error::{Error, XmlError};
+use crate::feed::{Feed, ParseOpts};
+use crate::parser::read_entry;
+use crate::util::decode;
+
+impl Feed {
+    /// Attempt to read an Atom feed from the reader, parsing its entries in parallel.
+    ///
+    /// The document is read into memory and scanned for the `<entry>` elements of the feed.
+    /// The rest of the feed is parsed first, then the entries are parsed on the rayon thread
+    /// pool and stored in document order. The result is the same as that of `read_from`, which
+    /// is used instead for documents in encodings other than UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Feed;
+    ///
+    /// # fn main() -> Result<(), atom_syndication::Error> {
+    /// let xml = r#"<feed><title>Feed Title</title><entry><id>urn:a</id></entry><entry><id>urn:b</id></entry></feed>"#;
+    /// let feed = Feed::read_from_parallel(xml.as_bytes())?;
+    /// assert_eq!(feed.title(), "Feed Title");
+    /// assert_eq!(feed.entries()[1].id(), "urn:b");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_from_parallel<B: BufRead>(reader: B) -> Result<Feed, Error> {
+        Feed::read_from_parallel_opts(reader, &ParseOpts::default())
+    }
+
+    /// Attempt to read an Atom feed from the reader with the given options, parsing its entries
+    /// in parallel like `read_from_parallel`.
+    pub fn read_from_parallel_opts<B: BufRead>(
+        mut reader: B,
+        opts: &ParseOpts,
+    ) -> Result<Feed, Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).map_err(XmlError::new)?;
+
+        let spans = match entry_spans(&bytes)? {
+            Some(spans) => spans,
+            None => return Feed::read_from_opts(bytes.as_slice(), opts),
+        };
+
+        let mut header = Vec::with_capacity(bytes.len());
+        let mut start = 0;
+        for span in &spans {
+            header.extend_from_slice(&bytes[start..span.start]);
+            start = span.end;
+        }
+        header.extend_from_slice(&bytes[start..]);
+        let mut feed = Feed::read_from_opts(header.as_slice(), opts)?;
+
+        feed.entries = spans
+            .par_iter()
+            .enumerate()
+            .map(|(index, span)| {
+                let mut entry =
+                    read_entry(&bytes[span.clone()], opts).map_err(|err| err.in_entry(index))?;
+                entry.apply_base(feed.base.as_deref());
+                Ok::<_, Error>(entry)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(feed)
+    }
+}
+
+/// Returns the byte ranges of the `<entry>` elements directly within the root element, or
+/// `None` if the document is not encoded in UTF-8.
+fn entry_spans(bytes: &[u8]) -> Result<Option<Vec<Range<usize>>>, Error> {
+    let mut reader = Reader::from_reader(bytes);
+    let mut spans = Vec::new();
+    let mut depth = 0;
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        let event = reader.read_event().map_err(XmlError::new)?;
+        let empty = matches!(event, Event::Empty(_));
+        match event {
+            Event::Decl(_) if reader.decoder().encoding().name() != "UTF-8" => return Ok(None),
+            Event::Start(element) | Event::Empty(element)
+                if depth == 1 && decode(element.name().as_ref(), &reader)? == "entry" =>
+            {
+                if !empty {
+                    reader.read_to_end(element.name()).map_err(XmlError::new)?;
+                }
+                spans.push(start..reader.buffer_position() as usize);
+            }
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth -= 1,
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(Some(spans))
+}
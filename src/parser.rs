@@ -0,0 +1,186 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::entry::Entry;
+use crate::error::{Error, XmlError};
+use crate::feed::{Feed, ParseOpts};
+use crate::fromxml::FromEmptyXml;
+
+/// The byte order marks of UTF-16, which cannot be scanned as bytes.
+const UTF16_BOMS: [&[u8]; 2] = [b"\xff\xfe", b"\xfe\xff"];
+
+/// Reads a feed from chunks of bytes as they arrive, e.g. from an async byte stream.
+///
+/// Each `<entry>` of the feed is parsed as soon as it has been received completely, so errors
+/// in it are returned by `feed_bytes` and only the parsed entry is kept instead of its bytes.
+/// The rest of the feed, such as its title and links, is kept as it is and parsed by `finish`.
+/// Chunks may be split anywhere, even within a tag or a multi-byte character. Documents in
+/// encodings other than UTF-8 are kept as a whole and parsed by `finish`.
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::FeedParser;
+///
+/// # fn main() -> Result<(), atom_syndication::Error> {
+/// let mut parser = FeedParser::new();
+/// parser.feed_bytes(b"<feed><title>Feed ")?;
+/// parser.feed_bytes(b"Title</title><entry><id>urn:a</id></en")?;
+/// parser.feed_bytes(b"try></fe")?;
+/// assert_eq!(parser.entries_parsed(), 1);
+/// parser.feed_bytes(b"ed>")?;
+/// let feed = parser.finish()?;
+/// assert_eq!(feed.title(), "Feed Title");
+/// assert_eq!(feed.entries()[0].id(), "urn:a");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FeedParser {
+    /// The bytes received but not scanned yet.
+    pending: Vec<u8>,
+    /// The bytes of the document outside of its entries.
+    header: Vec<u8>,
+    /// The entries parsed so far.
+    entries: Vec<Entry>,
+    /// The depth of elements at the end of the scanned bytes.
+    depth: usize,
+    /// Whether the document cannot be scanned, so it is kept as a whole.
+    whole: bool,
+    /// The number of bytes received so far.
+    received: usize,
+    opts: ParseOpts,
+}
+
+impl FeedParser {
+    /// Creates a parser using the default `ParseOpts`.
+    pub fn new() -> Self {
+        FeedParser::default()
+    }
+
+    /// Creates a parser using the given options.
+    pub fn with_opts(opts: ParseOpts) -> Self {
+        FeedParser {
+            opts,
+            ..FeedParser::default()
+        }
+    }
+
+    /// Appends the next chunk of the document and parses the entries it completes.
+    ///
+    /// Fails like `Feed::read_from_opts` if one of these entries is invalid. Errors in the rest
+    /// of the document are returned by `finish`.
+    pub fn feed_bytes(&mut self, chunk: &[u8]) -> Result<(), Error> {
+        self.received += chunk.len();
+        self.pending.extend_from_slice(chunk);
+        self.scan(false)
+    }
+
+    /// Returns the number of bytes received so far.
+    pub fn len(&self) -> usize {
+        self.received
+    }
+
+    /// Returns whether no bytes have been received yet.
+    pub fn is_empty(&self) -> bool {
+        self.received == 0
+    }
+
+    /// Returns the number of entries parsed so far.
+    pub fn entries_parsed(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Parses the rest of the feed and returns it with all of its entries.
+    ///
+    /// Fails like `Feed::read_from_opts`, which includes the case of an incomplete document.
+    pub fn finish(mut self) -> Result<Feed, Error> {
+        self.scan(true)?;
+        self.header.append(&mut self.pending);
+        let mut feed = Feed::read_from_opts(self.header.as_slice(), &self.opts)?;
+        for entry in &mut self.entries {
+            entry.apply_base(feed.base.as_deref());
+        }
+        self.entries.append(&mut feed.entries);
+        feed.entries = self.entries;
+        Ok(feed)
+    }
+
+    /// Moves the complete markup at the start of the pending bytes to the header, parsing the
+    /// entries of the feed on the way. Text at the end of the pending bytes is only moved if
+    /// `last` is set, as it may continue in the next chunk.
+    fn scan(&mut self, last: bool) -> Result<(), Error> {
+        if self.header.is_empty() && UTF16_BOMS.iter().any(|bom| self.pending.starts_with(bom)) {
+            self.whole = true;
+        }
+
+        let mut scanned = 0;
+        while !self.whole {
+            let rest = &self.pending[scanned..];
+            // the reader starts anywhere in the document, so it has not seen the start tags
+            let mut reader = Reader::from_reader(rest);
+            reader.config_mut().check_end_names = false;
+            reader.config_mut().allow_unmatched_ends = true;
+            // an error may be caused by markup which is not complete yet
+            let event = match reader.read_event() {
+                Ok(event) => event,
+                Err(_) => break,
+            };
+            let mut end = reader.buffer_position() as usize;
+            match event {
+                Event::Eof => break,
+                Event::Text(_) if end == rest.len() && !last => break,
+                Event::Decl(_) if reader.decoder().encoding().name() != "UTF-8" => {
+                    self.whole = true;
+                    break;
+                }
+                Event::Start(ref element) | Event::Empty(ref element)
+                    if self.depth == 1 && element.name().as_ref() == b"entry" =>
+                {
+                    if let Event::Start(ref element) = event {
+                        if reader.read_to_end(element.name()).is_err() {
+                            break;
+                        }
+                        end = reader.buffer_position() as usize;
+                    }
+                    let entry = read_entry(&rest[..end], &self.opts)
+                        .map_err(|err| err.in_entry(self.entries.len()))?;
+                    self.entries.push(entry);
+                    scanned += end;
+                    continue;
+                }
+                Event::Start(_) => self.depth += 1,
+                Event::End(_) => self.depth = self.depth.saturating_sub(1),
+                _ => {}
+            }
+            self.header.extend_from_slice(&rest[..end]);
+            scanned += end;
+        }
+
+        self.pending.drain(..scanned);
+        if self.whole {
+            self.header.append(&mut self.pending);
+        }
+        Ok(())
+    }
+}
+
+/// Reads an entry from the bytes of its `<entry>` element.
+pub(crate) fn read_entry(bytes: &[u8], opts: &ParseOpts) -> Result<Entry, Error> {
+    let mut reader = Reader::from_reader(bytes);
+    reader.config_mut().trim_text(opts.trim_whitespace);
+
+    let mut buf = Vec::new();
+    loop {
+        let event = reader.read_event_into(&mut buf).map_err(XmlError::new)?;
+        let empty = matches!(event, Event::Empty(_));
+        match event {
+            Event::Start(element) | Event::Empty(element) => {
+                return Entry::from_element(&mut reader, &element, empty, opts);
+            }
+            Event::Eof => return Err(Error::Eof),
+            _ => {}
+        }
+        buf.clear();
+    }
+}
@@ -1,19 +1,31 @@
-use std::borrow::Cow;
-use std::io::{BufRead, Write};
+use alloc::string::{String, ToString};
 
-use quick_xml::events::attributes::Attributes;
-use quick_xml::events::{BytesEnd, BytesStart, Event};
-use quick_xml::Reader;
-use quick_xml::Writer;
+#[cfg(feature = "std")]
+use std::{
+    borrow::Cow,
+    io::{BufRead, Write},
+};
 
-use crate::error::{Error, XmlError};
-use crate::fromxml::FromXml;
-use crate::toxml::{ToXmlNamed, WriterExt};
-use crate::util::{atom_text, decode, skip};
+#[cfg(feature = "std")]
+use quick_xml::{
+    events::{attributes::Attributes, BytesEnd, BytesStart, Event},
+    Reader, Writer,
+};
+
+#[cfg(feature = "std")]
+use crate::{
+    error::{Error, XmlError},
+    feed::ParseOpts,
+    fromxml::{FromEmptyXml, FromXml},
+    toxml::{ToXmlNamed, WriterExt},
+    util::{decode, element_text, skip},
+};
 
 /// Represents a person in an Atom feed
+///
+/// People are ordered by their name, then their email address and then their URI.
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "builders", derive(Builder))]
 #[cfg_attr(
     feature = "builders",
@@ -97,6 +109,55 @@ impl Person {
         self.email = email.into()
     }
 
+    /// Return the email address for this person if it looks like a real address.
+    ///
+    /// An address in angle brackets, as in `John Doe <johndoe@example.com>`, is extracted.
+    /// Obfuscated forms like `johndoe at example dot com` and other strings which do not look
+    /// like an address return `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Person;
+    ///
+    /// let mut person = Person::default();
+    /// person.set_email("John Doe <johndoe@example.com>".to_string());
+    /// assert_eq!(person.email_address(), Some("johndoe@example.com"));
+    ///
+    /// person.set_email("johndoe at example dot com".to_string());
+    /// assert_eq!(person.email_address(), None);
+    /// ```
+    pub fn email_address(&self) -> Option<&str> {
+        email_address(self.email.as_deref()?)
+    }
+
+    /// Set the email address for this person if it looks like a real address.
+    ///
+    /// The address is extracted as by `email_address`. Returns whether it was set; otherwise
+    /// the current email address is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Person;
+    ///
+    /// let mut person = Person::default();
+    /// assert!(person.set_email_checked("John Doe <johndoe@example.com>"));
+    /// assert_eq!(person.email(), Some("johndoe@example.com"));
+    ///
+    /// assert!(!person.set_email_checked("johndoe at example dot com"));
+    /// assert_eq!(person.email(), Some("johndoe@example.com"));
+    /// ```
+    pub fn set_email_checked(&mut self, email: &str) -> bool {
+        match email_address(email) {
+            Some(address) => {
+                self.email = Some(address.to_string());
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Return the Web page for this person.
     ///
     /// # Examples
@@ -112,6 +173,24 @@ impl Person {
         self.uri.as_deref()
     }
 
+    /// Return the URI of the person parsed as a `Url`.
+    ///
+    /// Returns `None` if it is not set. The stored string is left untouched if it fails to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Person;
+    ///
+    /// let mut person = Person::default();
+    /// person.set_uri("http://example.com".to_string());
+    /// assert_eq!(person.uri_url().unwrap().unwrap().as_str(), "http://example.com/");
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn uri_url(&self) -> Option<Result<url::Url, url::ParseError>> {
+        self.uri.as_deref().map(url::Url::parse)
+    }
+
     /// Set the Web page for this person.
     ///
     /// # Examples
@@ -130,19 +209,59 @@ impl Person {
     }
 }
 
+/// Extracts a plausible email address, optionally from the `Name <address>` form.
+fn email_address(email: &str) -> Option<&str> {
+    let email = email.trim();
+    let address = match email.rfind('<') {
+        Some(start) => email[start + 1..].strip_suffix('>')?,
+        None => email,
+    };
+    let (local, domain) = address.split_once('@')?;
+    let is_valid = |c: char| {
+        !c.is_whitespace()
+            && !c.is_control()
+            && !matches!(
+                c,
+                '<' | '>' | '(' | ')' | ',' | ';' | ':' | '"' | '[' | ']' | '\\' | '@'
+            )
+    };
+    let plausible = !local.is_empty()
+        && local.chars().all(is_valid)
+        && domain.contains('.')
+        && domain.split('.').all(|label| !label.is_empty())
+        && domain.chars().all(is_valid);
+    if plausible {
+        Some(address)
+    } else {
+        None
+    }
+}
+
+#[cfg(feature = "std")]
 impl FromXml for Person {
-    fn from_xml<B: BufRead>(reader: &mut Reader<B>, _: Attributes<'_>) -> Result<Self, Error> {
+    fn from_xml<B: BufRead>(
+        reader: &mut Reader<B>,
+        _: Attributes<'_>,
+        opts: &ParseOpts,
+    ) -> Result<Self, Error> {
         let mut person = Person::default();
         let mut buf = Vec::new();
 
         loop {
-            match reader.read_event_into(&mut buf).map_err(XmlError::new)? {
-                Event::Start(element) => match decode(element.name().as_ref(), reader)? {
-                    Cow::Borrowed("name") => person.name = atom_text(reader)?.unwrap_or_default(),
-                    Cow::Borrowed("email") => person.email = atom_text(reader)?,
-                    Cow::Borrowed("uri") => person.uri = atom_text(reader)?,
-                    _ => skip(element.name(), reader)?,
-                },
+            let event = reader.read_event_into(&mut buf).map_err(XmlError::new)?;
+            let empty = matches!(event, Event::Empty(_));
+            match event {
+                Event::Start(element) | Event::Empty(element) => {
+                    match decode(element.name().as_ref(), reader)? {
+                        Cow::Borrowed("name") => {
+                            person.name = element_text(reader, empty, opts)?.unwrap_or_default()
+                        }
+                        Cow::Borrowed("email") => person.email = element_text(reader, empty, opts)?,
+                        Cow::Borrowed("uri") => person.uri = element_text(reader, empty, opts)?,
+                        _ if !empty => skip(element.name(), reader)?,
+                        _ => {}
+                    }
+                }
                 Event::End(_) => break,
                 Event::Eof => return Err(Error::Eof),
                 _ => {}
@@ -155,6 +274,18 @@ impl FromXml for Person {
     }
 }
 
+#[cfg(feature = "std")]
+impl FromEmptyXml for Person {
+    fn from_empty_xml<B: BufRead>(
+        _: &mut Reader<B>,
+        _: Attributes<'_>,
+        _: &ParseOpts,
+    ) -> Result<Self, Error> {
+        Ok(Person::default())
+    }
+}
+
+#[cfg(feature = "std")]
 impl ToXmlNamed for Person {
     fn to_xml_named<W>(&self, writer: &mut Writer<W>, name: &str) -> Result<(), XmlError>
     where
@@ -188,3 +319,32 @@ impl PersonBuilder {
         self.build_impl().unwrap()
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_email_address() {
+        let cases = [
+            ("johndoe@example.com", Some("johndoe@example.com")),
+            (" johndoe@example.com ", Some("johndoe@example.com")),
+            ("<johndoe@example.com>", Some("johndoe@example.com")),
+            (
+                "\"Doe, John\" <john.doe+feed@mail.example.com>",
+                Some("john.doe+feed@mail.example.com"),
+            ),
+            ("johndoe at example dot com", None),
+            ("johndoe@example", None),
+            ("@example.com", None),
+            ("johndoe@", None),
+            ("john@doe@example.com", None),
+            ("johndoe@example..com", None),
+            ("John Doe <johndoe@example.com", None),
+            ("", None),
+        ];
+        for (email, expected) in cases {
+            assert_eq!(email_address(email), expected, "{}", email);
+        }
+    }
+}
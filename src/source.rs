@@ -1,20 +1,35 @@
-use std::borrow::Cow;
-use std::io::{BufRead, Write};
+use alloc::string::String;
+use alloc::vec::Vec;
 
-use quick_xml::events::attributes::Attributes;
-use quick_xml::events::{BytesEnd, BytesStart, Event};
-use quick_xml::Reader;
-use quick_xml::Writer;
+#[cfg(feature = "std")]
+use std::{
+    borrow::Cow,
+    io::{BufRead, Write},
+};
+
+#[cfg(feature = "std")]
+use quick_xml::{
+    events::{attributes::Attributes, BytesEnd, BytesStart, Event},
+    Reader, Writer,
+};
 
 use crate::category::Category;
-use crate::error::{Error, XmlError};
-use crate::fromxml::FromXml;
+use crate::feed::Feed;
 use crate::generator::Generator;
 use crate::link::Link;
 use crate::person::Person;
 use crate::text::Text;
-use crate::toxml::{ToXml, WriterExt};
-use crate::util::{atom_datetime, atom_text, decode, default_fixed_datetime, skip, FixedDateTime};
+#[cfg(feature = "builders")]
+use crate::util::parse_rfc3339;
+use crate::util::{default_fixed_datetime, FixedDateTime};
+#[cfg(feature = "std")]
+use crate::{
+    error::{Error, XmlError},
+    feed::{ParseOpts, WriteConfig},
+    fromxml::{FromEmptyXml, FromXml},
+    toxml::{ToXml, WriterExt},
+    util::{decode, element_datetime, element_text, format_datetime, skip},
+};
 
 /// Represents the source of an Atom entry
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
@@ -175,6 +190,21 @@ impl Source {
         self.authors.as_slice()
     }
 
+    /// Return a mutable reference to the authors of the source feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Source, Person};
+    ///
+    /// let mut source = Source::default();
+    /// source.authors_mut().push(Person::default());
+    /// assert_eq!(source.authors().len(), 1);
+    /// ```
+    pub fn authors_mut(&mut self) -> &mut Vec<Person> {
+        &mut self.authors
+    }
+
     /// Set the authors of the source feed.
     ///
     /// # Examples
@@ -207,6 +237,21 @@ impl Source {
         self.categories.as_slice()
     }
 
+    /// Return a mutable reference to the categories the source feed belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Source, Category};
+    ///
+    /// let mut source = Source::default();
+    /// source.categories_mut().push(Category::default());
+    /// assert_eq!(source.categories().len(), 1);
+    /// ```
+    pub fn categories_mut(&mut self) -> &mut Vec<Category> {
+        &mut self.categories
+    }
+
     /// Set the categories the source feed belongs to.
     ///
     /// # Examples
@@ -239,6 +284,21 @@ impl Source {
         self.contributors.as_slice()
     }
 
+    /// Return a mutable reference to the contributors to the source feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Source, Person};
+    ///
+    /// let mut source = Source::default();
+    /// source.contributors_mut().push(Person::default());
+    /// assert_eq!(source.contributors().len(), 1);
+    /// ```
+    pub fn contributors_mut(&mut self) -> &mut Vec<Person> {
+        &mut self.contributors
+    }
+
     /// Set the contributors to the source feed.
     ///
     /// # Examples
@@ -303,6 +363,24 @@ impl Source {
         self.icon.as_deref()
     }
 
+    /// Return the URL of the icon parsed as a `Url`.
+    ///
+    /// Returns `None` if it is not set. The stored string is left untouched if it fails to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Source;
+    ///
+    /// let mut source = Source::default();
+    /// source.set_icon("http://example.com/icon.png".to_string());
+    /// assert_eq!(source.icon_url().unwrap().unwrap().as_str(), "http://example.com/icon.png");
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn icon_url(&self) -> Option<Result<url::Url, url::ParseError>> {
+        self.icon.as_deref().map(url::Url::parse)
+    }
+
     /// Set the icon for the source feed.
     ///
     /// # Examples
@@ -335,6 +413,22 @@ impl Source {
         self.links.as_slice()
     }
 
+    /// Return a mutable reference to the Web pages related to the source feed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Source, Link};
+    ///
+    /// let mut source = Source::default();
+    /// source.set_links(vec![Link::default()]);
+    /// source.links_mut()[0].set_href("http://example.com/");
+    /// assert_eq!(source.links()[0].href(), "http://example.com/");
+    /// ```
+    pub fn links_mut(&mut self) -> &mut Vec<Link> {
+        &mut self.links
+    }
+
     /// Set the Web pages related to the source feed.
     ///
     /// # Examples
@@ -367,6 +461,24 @@ impl Source {
         self.logo.as_deref()
     }
 
+    /// Return the URL of the logo parsed as a `Url`.
+    ///
+    /// Returns `None` if it is not set. The stored string is left untouched if it fails to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Source;
+    ///
+    /// let mut source = Source::default();
+    /// source.set_logo("http://example.com/logo.png".to_string());
+    /// assert_eq!(source.logo_url().unwrap().unwrap().as_str(), "http://example.com/logo.png");
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn logo_url(&self) -> Option<Result<url::Url, url::ParseError>> {
+        self.logo.as_deref().map(url::Url::parse)
+    }
+
     /// Set the logo for the source feed.
     ///
     /// # Examples
@@ -447,53 +559,86 @@ impl Source {
     {
         self.subtitle = subtitle.into()
     }
+
+    /// Records the base URL in scope of the text constructs and links of this source, given
+    /// the base URL `parent` in scope of the `<source>` element.
+    #[cfg(feature = "std")]
+    pub(crate) fn apply_base(&mut self, parent: Option<&str>) {
+        self.title.apply_base(parent);
+        for text in self.rights.iter_mut().chain(self.subtitle.iter_mut()) {
+            text.apply_base(parent);
+        }
+        for link in &mut self.links {
+            link.apply_base(parent);
+        }
+    }
 }
 
+#[cfg(feature = "std")]
 impl FromXml for Source {
-    fn from_xml<B: BufRead>(reader: &mut Reader<B>, _: Attributes<'_>) -> Result<Self, Error> {
+    fn from_xml<B: BufRead>(
+        reader: &mut Reader<B>,
+        _: Attributes<'_>,
+        opts: &ParseOpts,
+    ) -> Result<Self, Error> {
         let mut source = Source::default();
+        let mut updated = None;
         let mut buf = Vec::new();
 
         loop {
-            match reader.read_event_into(&mut buf).map_err(XmlError::new)? {
-                Event::Start(element) => match decode(element.name().as_ref(), reader)? {
-                    Cow::Borrowed("id") => source.id = atom_text(reader)?.unwrap_or_default(),
-                    Cow::Borrowed("title") => {
-                        source.title = Text::from_xml(reader, element.attributes())?
-                    }
-                    Cow::Borrowed("updated") => {
-                        source.updated =
-                            atom_datetime(reader)?.unwrap_or_else(default_fixed_datetime)
+            let event = reader.read_event_into(&mut buf).map_err(XmlError::new)?;
+            let empty = matches!(event, Event::Empty(_));
+            match event {
+                Event::Start(element) | Event::Empty(element) => {
+                    match decode(element.name().as_ref(), reader)? {
+                        Cow::Borrowed("id") => {
+                            source.id = element_text(reader, empty, opts)?.unwrap_or_default()
+                        }
+                        Cow::Borrowed("title") => {
+                            source.title = Text::from_element(reader, &element, empty, opts)?
+                        }
+                        Cow::Borrowed("updated") => {
+                            updated = element_datetime(reader, empty, "source.updated", opts)?
+                        }
+                        Cow::Borrowed("author") => source
+                            .authors
+                            .push(Person::from_element(reader, &element, empty, opts)?),
+                        Cow::Borrowed("category") => {
+                            source
+                                .categories
+                                .push(Category::from_xml(reader, &element, opts)?);
+                            if !empty {
+                                skip(element.name(), reader)?;
+                            }
+                        }
+                        Cow::Borrowed("contributor") => source
+                            .contributors
+                            .push(Person::from_element(reader, &element, empty, opts)?),
+                        Cow::Borrowed("generator") => {
+                            source.generator =
+                                Some(Generator::from_element(reader, &element, empty, opts)?)
+                        }
+                        Cow::Borrowed("icon") => source.icon = element_text(reader, empty, opts)?,
+                        Cow::Borrowed("link") => {
+                            if let Some(link) = Link::from_xml(reader, &element, opts)? {
+                                source.links.push(link);
+                            }
+                            if !empty {
+                                skip(element.name(), reader)?;
+                            }
+                        }
+                        Cow::Borrowed("logo") => source.logo = element_text(reader, empty, opts)?,
+                        Cow::Borrowed("rights") => {
+                            source.rights = Some(Text::from_element(reader, &element, empty, opts)?)
+                        }
+                        Cow::Borrowed("subtitle") => {
+                            source.subtitle =
+                                Some(Text::from_element(reader, &element, empty, opts)?)
+                        }
+                        _ if !empty => skip(element.name(), reader)?,
+                        _ => {}
                     }
-                    Cow::Borrowed("author") => source
-                        .authors
-                        .push(Person::from_xml(reader, element.attributes())?),
-                    Cow::Borrowed("category") => {
-                        source
-                            .categories
-                            .push(Category::from_xml(reader, &element)?);
-                        skip(element.name(), reader)?;
-                    }
-                    Cow::Borrowed("contributor") => source
-                        .contributors
-                        .push(Person::from_xml(reader, element.attributes())?),
-                    Cow::Borrowed("generator") => {
-                        source.generator = Some(Generator::from_xml(reader, element.attributes())?)
-                    }
-                    Cow::Borrowed("icon") => source.icon = atom_text(reader)?,
-                    Cow::Borrowed("link") => {
-                        source.links.push(Link::from_xml(reader, &element)?);
-                        skip(element.name(), reader)?;
-                    }
-                    Cow::Borrowed("logo") => source.logo = atom_text(reader)?,
-                    Cow::Borrowed("rights") => {
-                        source.rights = Some(Text::from_xml(reader, element.attributes())?)
-                    }
-                    Cow::Borrowed("subtitle") => {
-                        source.subtitle = Some(Text::from_xml(reader, element.attributes())?)
-                    }
-                    _ => skip(element.name(), reader)?,
-                },
+                }
                 Event::End(_) => break,
                 Event::Eof => return Err(Error::Eof),
                 _ => {}
@@ -502,19 +647,53 @@ impl FromXml for Source {
             buf.clear();
         }
 
+        source.updated = match updated {
+            Some(updated) => updated,
+            None => opts.missing_updated.resolve()?,
+        };
+
         Ok(source)
     }
 }
 
+#[cfg(feature = "std")]
+impl FromEmptyXml for Source {
+    fn from_empty_xml<B: BufRead>(
+        _: &mut Reader<B>,
+        _: Attributes<'_>,
+        opts: &ParseOpts,
+    ) -> Result<Self, Error> {
+        Ok(Source {
+            updated: opts.missing_updated.resolve()?,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(feature = "std")]
 impl ToXml for Source {
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+        self.to_xml_with_config(writer, &WriteConfig::default())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Source {
+    /// Writes this source according to the options of `config`.
+    pub(crate) fn to_xml_with_config<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        config: &WriteConfig,
+    ) -> Result<(), XmlError> {
         let name = "source";
         writer
             .write_event(Event::Start(BytesStart::new(name)))
             .map_err(XmlError::new)?;
-        writer.write_object_named(&self.title, "title")?;
-        writer.write_text_element("id", &self.id)?;
-        writer.write_text_element("updated", &self.updated.to_rfc3339())?;
+        if !config.rfc_element_order {
+            writer.write_object_named(&self.title, "title")?;
+            writer.write_text_element("id", &self.id)?;
+            writer.write_text_element("updated", &format_datetime(&self.updated, config))?;
+        }
         writer.write_objects_named(&self.authors, "author")?;
         writer.write_objects(&self.categories)?;
         writer.write_objects_named(&self.contributors, "contributor")?;
@@ -527,7 +706,13 @@ impl ToXml for Source {
             writer.write_text_element("icon", icon)?;
         }
 
-        writer.write_objects(&self.links)?;
+        if config.rfc_element_order {
+            writer.write_text_element("id", &self.id)?;
+        }
+
+        for link in &self.links {
+            link.to_xml_with_config(writer, config)?;
+        }
 
         if let Some(ref logo) = self.logo {
             writer.write_text_element("logo", logo)?;
@@ -541,6 +726,11 @@ impl ToXml for Source {
             writer.write_object_named(subtitle, "subtitle")?;
         }
 
+        if config.rfc_element_order {
+            writer.write_object_named(&self.title, "title")?;
+            writer.write_text_element("updated", &format_datetime(&self.updated, config))?;
+        }
+
         writer
             .write_event(Event::End(BytesEnd::new(name)))
             .map_err(XmlError::new)?;
@@ -568,10 +758,73 @@ impl Default for Source {
     }
 }
 
+impl From<&Feed> for Source {
+    /// Creates a `Source` describing the given feed, for use on entries copied out of it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::{Feed, Source};
+    ///
+    /// let mut feed = Feed::default();
+    /// feed.set_id("urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6");
+    ///
+    /// let source = Source::from(&feed);
+    /// assert_eq!(source.id(), feed.id());
+    /// ```
+    fn from(feed: &Feed) -> Self {
+        Source {
+            title: feed.title.clone(),
+            id: feed.id.clone(),
+            updated: feed.updated,
+            authors: feed.authors.clone(),
+            categories: feed.categories.clone(),
+            contributors: feed.contributors.clone(),
+            generator: feed.generator.clone(),
+            icon: feed.icon.clone(),
+            links: feed.links.clone(),
+            logo: feed.logo.clone(),
+            rights: feed.rights.clone(),
+            subtitle: feed.subtitle.clone(),
+        }
+    }
+}
+
 #[cfg(feature = "builders")]
 impl SourceBuilder {
     /// Builds a new `Source`.
     pub fn build(&self) -> Source {
         self.build_impl().unwrap()
     }
+
+    clear_methods! {
+        SourceBuilder => source {
+            clear_authors: authors, author(Person);
+            clear_categories: categories, category(Category);
+            clear_contributors: contributors, contributor(Person);
+            clear_links: links, link(Link);
+        }
+    }
+
+    /// Sets the last time the source was modified from an RFC 3339 timestamp.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::SourceBuilder;
+    ///
+    /// # fn main() -> Result<(), atom_syndication::Error> {
+    /// let source = SourceBuilder::default()
+    ///     .updated_rfc3339("2017-06-03T15:15:44-05:00")?
+    ///     .build();
+    /// assert_eq!(source.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    ///
+    /// assert!(SourceBuilder::default().updated_rfc3339("yesterday").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn updated_rfc3339(&mut self, updated: &str) -> Result<&mut Self, Error> {
+        self.updated = Some(parse_rfc3339("source.updated", updated)?);
+        Ok(self)
+    }
 }
@@ -0,0 +1,62 @@
+use std::fmt::Write;
+
+use crate::feed::Feed;
+
+/// The number of lines shown around the first difference of a failed roundtrip.
+const CONTEXT_LINES: usize = 3;
+
+/// Asserts that the feed is equal to itself after being written and read again.
+///
+/// On mismatch, panics with the written XML and the first difference between the `Debug`
+/// representations of both feeds.
+///
+/// # Examples
+///
+/// ```
+/// use atom_syndication::test_util::assert_roundtrips;
+/// use atom_syndication::Feed;
+///
+/// let feed = Feed {
+///     title: "Feed Title".into(),
+///     id: "urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6".into(),
+///     ..Default::default()
+/// };
+/// assert_roundtrips(&feed);
+/// ```
+#[track_caller]
+pub fn assert_roundtrips(feed: &Feed) {
+    let xml = feed.to_string();
+    let reread = match Feed::read_from(xml.as_bytes()) {
+        Ok(reread) => reread,
+        Err(err) => panic!("written feed could not be read: {}\n\n{}", err, xml),
+    };
+    if reread == *feed {
+        return;
+    }
+
+    let expected = format!("{:#?}", feed);
+    let actual = format!("{:#?}", reread);
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+    let first = expected
+        .iter()
+        .zip(&actual)
+        .position(|(expected, actual)| expected != actual)
+        .unwrap_or_else(|| expected.len().min(actual.len()));
+    let start = first.saturating_sub(CONTEXT_LINES);
+
+    let mut diff = String::new();
+    for line in &expected[start..first] {
+        let _ = writeln!(diff, "  {}", line);
+    }
+    for line in expected.iter().skip(first).take(CONTEXT_LINES) {
+        let _ = writeln!(diff, "- {}", line);
+    }
+    for line in actual.iter().skip(first).take(CONTEXT_LINES) {
+        let _ = writeln!(diff, "+ {}", line);
+    }
+    panic!(
+        "feed did not roundtrip (- original, + reread):\n{}\nwritten XML:\n{}",
+        diff, xml
+    );
+}
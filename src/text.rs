@@ -1,19 +1,32 @@
-use std::borrow::Cow;
-use std::cmp::PartialEq;
-use std::convert::{AsRef, From};
-use std::io::{BufRead, Write};
-use std::ops::Deref;
-use std::str::FromStr;
-
-use quick_xml::events::attributes::Attributes;
-use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
-use quick_xml::Reader;
-use quick_xml::Writer;
-
-use crate::error::{Error, XmlError};
-use crate::fromxml::FromXml;
-use crate::toxml::ToXmlNamed;
-use crate::util::{atom_text, atom_xhtml, attr_value, decode};
+use alloc::string::String;
+use core::cmp::PartialEq;
+use core::convert::{AsRef, From};
+use core::fmt;
+use core::ops::Deref;
+
+use crate::util::decode_double_encoded;
+
+#[cfg(feature = "std")]
+use std::{
+    borrow::Cow,
+    io::{BufRead, Write},
+    str::FromStr,
+};
+
+#[cfg(feature = "std")]
+use quick_xml::{
+    events::{attributes::Attributes, BytesEnd, BytesStart, BytesText, Event},
+    Reader, Writer,
+};
+
+#[cfg(feature = "std")]
+use crate::{
+    error::{Error, XmlError},
+    feed::ParseOpts,
+    fromxml::{FromEmptyXml, FromXml},
+    toxml::ToXmlNamed,
+    util::{atom_text_limited, atom_xhtml_limited, attr_value, decode, join_base},
+};
 
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -28,6 +41,7 @@ pub enum TextType {
     Xhtml,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for TextType {
     fn default() -> Self {
         TextType::Text
@@ -35,15 +49,45 @@ impl Default for TextType {
 }
 
 impl TextType {
-    fn as_str(&self) -> &'static str {
+    /// Return the value of the `type` attribute for this text type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::TextType;
+    ///
+    /// assert_eq!(TextType::Xhtml.as_str(), "xhtml");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
         match self {
             Self::Text => "text",
             Self::Html => "html",
             Self::Xhtml => "xhtml",
         }
     }
+
+    /// Return whether the text contains markup, i.e. is HTML or XHTML.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::TextType;
+    ///
+    /// assert!(TextType::Html.is_markup());
+    /// assert!(!TextType::Text.is_markup());
+    /// ```
+    pub fn is_markup(&self) -> bool {
+        matches!(self, Self::Html | Self::Xhtml)
+    }
 }
 
+impl fmt::Display for TextType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
 impl FromStr for TextType {
     type Err = Error;
 
@@ -61,7 +105,7 @@ impl FromStr for TextType {
 }
 
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, Default)]
 #[cfg_attr(feature = "builders", derive(Builder))]
 #[cfg_attr(
     feature = "builders",
@@ -77,10 +121,36 @@ pub struct Text {
     pub value: String,
     /// Base URL for resolving any relative references found in the element.
     pub base: Option<String>,
+    /// The base URL in scope of the element when it was read, which combines the `xml:base` of
+    /// the feed, the entry and the element itself.
+    ///
+    /// It is derived from the document, so it is neither written, serialized nor compared.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "builders", builder(setter(skip)))]
+    pub effective_base: Option<String>,
     /// Indicates the natural language for the element.
     pub lang: Option<String>,
     /// Type of content stored in the element.
     pub r#type: TextType,
+    /// Whether the `type` attribute is written when it is `"text"`, which is the default and
+    /// otherwise left out. Set when reading an element with an explicit `type="text"`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub explicit_type: bool,
+    /// Whether the value was cut off when reading because it exceeded
+    /// `ParseOpts::max_content_len`, which only applies to summaries.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub truncated: bool,
+}
+
+impl PartialEq for Text {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+            && self.base == other.base
+            && self.lang == other.lang
+            && self.r#type == other.r#type
+            && self.explicit_type == other.explicit_type
+            && self.truncated == other.truncated
+    }
 }
 
 impl Text {
@@ -111,10 +181,105 @@ impl Text {
         }
     }
 
+    /// Sets the natural language of the text construct (xml:lang).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Text;
+    ///
+    /// let title = Text::plain("Feed Title").with_lang("en");
+    /// assert_eq!(title.lang.as_deref(), Some("en"));
+    /// ```
+    pub fn with_lang(mut self, lang: impl Into<String>) -> Self {
+        self.lang = Some(lang.into());
+        self
+    }
+
+    /// Sets the base URL of the text construct (xml:base).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Text;
+    ///
+    /// let title = Text::html("<a href=\"about.html\">About</a>").with_base("http://example.com/");
+    /// assert_eq!(title.base.as_deref(), Some("http://example.com/"));
+    /// ```
+    pub fn with_base(mut self, base: impl Into<String>) -> Self {
+        self.base = Some(base.into());
+        self
+    }
+
     /// Returns a content as a `str`
     pub fn as_str(&self) -> &str {
         &self.value
     }
+
+    /// Returns the value with scripts, event handlers and other markup which is unsafe to
+    /// render removed, using the default allowlist of `ammonia`.
+    ///
+    /// Returns `None` for plain text, which has no markup and must be escaped rather than
+    /// sanitized when rendered as HTML.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Text;
+    ///
+    /// let title = Text::html("<b onclick=\"steal()\">Bold</b><script>steal()</script>");
+    /// assert_eq!(title.sanitized_html().as_deref(), Some("<b>Bold</b>"));
+    /// assert_eq!(Text::plain("<b>Bold</b>").sanitized_html(), None);
+    /// ```
+    #[cfg(feature = "sanitize")]
+    pub fn sanitized_html(&self) -> Option<String> {
+        match self.r#type {
+            TextType::Html | TextType::Xhtml => Some(ammonia::clean(&self.value)),
+            TextType::Text => None,
+        }
+    }
+}
+
+impl Text {
+    /// Remove a second layer of escaping from the value of HTML text, and return whether it
+    /// was found.
+    ///
+    /// Some generators escape markup twice, so that `<b>` is read as `&lt;b&gt;` and would be
+    /// shown as such. HTML text without any `<` but with an escaped `&lt;` is unescaped once.
+    /// Other text is left unchanged, as is HTML text which does not look double-encoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atom_syndication::Text;
+    ///
+    /// let mut title = Text::html("&lt;b&gt;Fish &amp;amp; chips&lt;/b&gt;");
+    /// assert!(title.decode_once_more());
+    /// assert_eq!(title.value, "<b>Fish &amp; chips</b>");
+    /// assert!(!title.decode_once_more());
+    ///
+    /// let mut title = Text::plain("&lt;b&gt;");
+    /// assert!(!title.decode_once_more());
+    /// ```
+    pub fn decode_once_more(&mut self) -> bool {
+        if self.r#type != TextType::Html {
+            return false;
+        }
+        match decode_double_encoded(&self.value) {
+            Some(value) => {
+                self.value = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Records the effective base URL of this element, given the base URL `parent` in scope
+    /// of it.
+    #[cfg(feature = "std")]
+    pub(crate) fn apply_base(&mut self, parent: Option<&str>) {
+        self.effective_base = join_base(parent, self.base.as_deref());
+    }
 }
 
 impl From<String> for Text {
@@ -155,38 +320,77 @@ impl PartialEq<Text> for str {
     }
 }
 
+#[cfg(feature = "std")]
 impl FromXml for Text {
     fn from_xml<B: BufRead>(
+        reader: &mut Reader<B>,
+        atts: Attributes<'_>,
+        opts: &ParseOpts,
+    ) -> Result<Self, Error> {
+        Text::from_xml_limited(reader, atts, opts, None)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Text {
+    /// Reads a text construct like `from_xml`, keeping at most `limit` bytes of its value.
+    pub(crate) fn from_xml_limited<B: BufRead>(
+        reader: &mut Reader<B>,
+        atts: Attributes<'_>,
+        opts: &ParseOpts,
+        limit: Option<usize>,
+    ) -> Result<Self, Error> {
+        let mut text = Text::from_empty_xml(reader, atts, opts)?;
+
+        let (content, truncated) = if text.r#type == TextType::Xhtml {
+            atom_xhtml_limited(reader, opts, limit)?
+        } else {
+            atom_text_limited(reader, opts, limit)?
+        };
+
+        text.value = content.unwrap_or_default();
+        text.truncated = truncated;
+
+        Ok(text)
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromEmptyXml for Text {
+    fn from_empty_xml<B: BufRead>(
         reader: &mut Reader<B>,
         mut atts: Attributes<'_>,
+        opts: &ParseOpts,
     ) -> Result<Self, Error> {
         let mut text = Text::default();
 
         for att in atts.with_checks(false).flatten() {
             match decode(att.key.as_ref(), reader)? {
                 Cow::Borrowed("xml:base") => {
-                    text.base = Some(attr_value(&att, reader)?.to_string())
+                    text.base = Some(attr_value(&att, reader, opts)?.into_owned())
                 }
                 Cow::Borrowed("xml:lang") => {
-                    text.lang = Some(attr_value(&att, reader)?.to_string())
+                    text.lang = Some(attr_value(&att, reader, opts)?.into_owned())
+                }
+                Cow::Borrowed("type") => {
+                    text.r#type = match attr_value(&att, reader, opts)?.parse() {
+                        Ok(r#type) => {
+                            text.explicit_type = r#type == TextType::Text;
+                            r#type
+                        }
+                        Err(_) if opts.lenient_text_type => TextType::Text,
+                        Err(err) => return Err(err),
+                    }
                 }
-                Cow::Borrowed("type") => text.r#type = attr_value(&att, reader)?.parse()?,
                 _ => {}
             }
         }
 
-        let content = if text.r#type == TextType::Xhtml {
-            atom_xhtml(reader)?
-        } else {
-            atom_text(reader)?
-        };
-
-        text.value = content.unwrap_or_default();
-
         Ok(text)
     }
 }
 
+#[cfg(feature = "std")]
 impl ToXmlNamed for Text {
     fn to_xml_named<W>(&self, writer: &mut Writer<W>, name: &str) -> Result<(), XmlError>
     where
@@ -199,7 +403,7 @@ impl ToXmlNamed for Text {
         if let Some(ref lang) = self.lang {
             element.push_attribute(("xml:lang", lang.as_str()));
         }
-        if self.r#type != TextType::default() {
+        if self.r#type != TextType::default() || self.explicit_type {
             element.push_attribute(("type", self.r#type.as_str()));
         }
         writer
@@ -1,27 +1,38 @@
-use std::io::Write;
+use std::borrow::Cow;
+use std::fmt;
+use std::io::{self, Write};
+use std::mem;
+use std::str;
 
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Writer;
 
 use crate::error::XmlError;
 
-pub(crate) trait ToXml {
+/// A type which can be written as an XML element.
+pub trait ToXml {
+    /// Writes the value as a complete element, from its start tag to its end tag.
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError>;
 }
 
-impl<'a, T: ToXml> ToXml for &'a T {
+impl<T: ToXml> ToXml for &T {
     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
         (*self).to_xml(writer)
     }
 }
 
-pub(crate) trait ToXmlNamed {
+/// A type which can be written as an XML element with a name chosen by the caller.
+///
+/// This is used for constructs like text and persons, which are written as e.g. `<title>` or
+/// `<author>` depending on where they appear.
+pub trait ToXmlNamed {
+    /// Writes the value as a complete element named `name`.
     fn to_xml_named<W>(&self, writer: &mut Writer<W>, name: &str) -> Result<(), XmlError>
     where
         W: Write;
 }
 
-impl<'a, T: ToXmlNamed> ToXmlNamed for &'a T {
+impl<T: ToXmlNamed> ToXmlNamed for &T {
     fn to_xml_named<W>(&self, writer: &mut Writer<W>, name: &str) -> Result<(), XmlError>
     where
         W: Write,
@@ -101,3 +112,114 @@ impl<W: Write> WriterExt for Writer<W> {
         Ok(())
     }
 }
+
+/// A writer which counts the bytes passed to the wrapped writer.
+pub(crate) struct CountingWriter<W> {
+    inner: W,
+    count: usize,
+}
+
+impl<W: Write> CountingWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self { inner, count: 0 }
+    }
+
+    pub(crate) fn into_parts(self) -> (W, usize) {
+        (self.inner, self.count)
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.count += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A writer which writes `\r\n` in place of the line breaks separating markup.
+///
+/// `quick_xml` emits the line break of each indentation and the one following the document
+/// declaration by a dedicated write, so only such writes are converted and line breaks inside
+/// of text values are passed through unchanged.
+pub(crate) struct CrLfWriter<W> {
+    inner: W,
+}
+
+impl<W: Write> CrLfWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    pub(crate) fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for CrLfWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf == b"\n" {
+            self.inner.write_all(b"\r\n")?;
+            Ok(buf.len())
+        } else {
+            self.inner.write(buf)
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A writer which passes the written bytes to a `fmt::Write` sink such as a `fmt::Formatter`.
+///
+/// A multi-byte character split across two writes is held back until it is complete.
+pub(crate) struct FmtWriter<W> {
+    inner: W,
+    pending: Vec<u8>,
+}
+
+impl<W: fmt::Write> FmtWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl<W: fmt::Write> Write for FmtWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let bytes = if self.pending.is_empty() {
+            Cow::Borrowed(buf)
+        } else {
+            let mut bytes = mem::take(&mut self.pending);
+            bytes.extend_from_slice(buf);
+            Cow::Owned(bytes)
+        };
+
+        let valid = match str::from_utf8(&bytes) {
+            Ok(s) => s,
+            Err(e) if e.error_len().is_none() => {
+                let (valid, rest) = bytes.split_at(e.valid_up_to());
+                self.pending = rest.to_vec();
+                // the prefix was just validated
+                str::from_utf8(valid).unwrap()
+            }
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        };
+
+        self.inner
+            .write_str(valid)
+            .map_err(|_| io::Error::from(io::ErrorKind::Other))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
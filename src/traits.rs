@@ -0,0 +1,77 @@
+//! The traits used to read and write the elements of a feed.
+//!
+//! They are implemented by the feed types and can be implemented by other crates to read and
+//! write their own elements, e.g. to provide typed access to an extension namespace. The
+//! signatures use the types of the `quick-xml` crate, which is re-exported as `quick_xml` to
+//! make sure the same version is used.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::io::{BufRead, Write};
+//!
+//! use atom_syndication::traits::quick_xml::events::attributes::Attributes;
+//! use atom_syndication::traits::quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+//! use atom_syndication::traits::quick_xml::{Reader, Writer};
+//! use atom_syndication::traits::{FromXml, ToXml};
+//! use atom_syndication::{Error, ParseOpts, XmlError};
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Duration(u64);
+//!
+//! impl FromXml for Duration {
+//!     fn from_xml<R: BufRead>(
+//!         reader: &mut Reader<R>,
+//!         _atts: Attributes<'_>,
+//!         _opts: &ParseOpts,
+//!     ) -> Result<Self, Error> {
+//!         let mut buf = Vec::new();
+//!         let mut seconds = 0;
+//!         loop {
+//!             match reader.read_event_into(&mut buf).map_err(XmlError::new)? {
+//!                 Event::Text(text) => {
+//!                     seconds = text.unescape().map_err(XmlError::new)?.parse().unwrap_or(0)
+//!                 }
+//!                 Event::End(_) => return Ok(Duration(seconds)),
+//!                 Event::Eof => return Err(Error::Eof),
+//!                 _ => {}
+//!             }
+//!             buf.clear();
+//!         }
+//!     }
+//! }
+//!
+//! impl ToXml for Duration {
+//!     fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), XmlError> {
+//!         let seconds = self.0.to_string();
+//!         writer
+//!             .write_event(Event::Start(BytesStart::new("duration")))
+//!             .map_err(XmlError::new)?;
+//!         writer
+//!             .write_event(Event::Text(BytesText::new(&seconds)))
+//!             .map_err(XmlError::new)?;
+//!         writer
+//!             .write_event(Event::End(BytesEnd::new("duration")))
+//!             .map_err(XmlError::new)?;
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let mut reader = Reader::from_str("<duration>90</duration>");
+//! let duration = match reader.read_event().unwrap() {
+//!     Event::Start(element) => {
+//!         Duration::from_xml(&mut reader, element.attributes(), &ParseOpts::default()).unwrap()
+//!     }
+//!     _ => unreachable!(),
+//! };
+//! assert_eq!(duration, Duration(90));
+//!
+//! let mut writer = Writer::new(Vec::new());
+//! duration.to_xml(&mut writer).unwrap();
+//! assert_eq!(writer.into_inner(), b"<duration>90</duration>");
+//! ```
+
+pub use quick_xml;
+
+pub use crate::fromxml::FromXml;
+pub use crate::toxml::{ToXml, ToXmlNamed};
@@ -1,14 +1,26 @@
+#[cfg(feature = "std")]
 use quick_xml::{
-    escape::escape,
+    escape::{escape, resolve_xml_entity},
     events::{attributes::Attribute, Event},
     name::QName,
     Reader,
 };
 
+#[cfg(feature = "std")]
 use crate::error::{Error, XmlError};
-use std::borrow::Cow;
+#[cfg(feature = "std")]
+use crate::feed::{ParseOpts, WriteConfig};
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::str::FromStr;
+#[cfg(feature = "std")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "std")]
+use chrono::{FixedOffset, SecondsFormat, TimeZone};
 
 /// Alias of `::chrono::DateTime<::chrono::FixedOffset>`
 pub type FixedDateTime = ::chrono::DateTime<::chrono::FixedOffset>;
@@ -17,24 +29,254 @@ pub fn default_fixed_datetime() -> FixedDateTime {
     FixedDateTime::from_str("1970-01-01T00:00:00Z").unwrap()
 }
 
-pub(crate) fn decode<'s, 'r, B: BufRead>(
+/// The components of a URI reference, as split by RFC 3986 appendix B.
+struct UriRef<'a> {
+    scheme: Option<&'a str>,
+    authority: Option<&'a str>,
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl<'a> UriRef<'a> {
+    fn parse(uri: &'a str) -> Self {
+        let (rest, fragment) = match uri.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment)),
+            None => (uri, None),
+        };
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query)),
+            None => (rest, None),
+        };
+        let (scheme, rest) = match rest.split_once(':') {
+            Some((scheme, rest)) if is_scheme(scheme) => (Some(scheme), rest),
+            _ => (None, rest),
+        };
+        let (authority, path) = match rest.strip_prefix("//") {
+            Some(rest) => match rest.find('/') {
+                Some(i) => (Some(&rest[..i]), &rest[i..]),
+                None => (Some(rest), ""),
+            },
+            None => (None, rest),
+        };
+        UriRef {
+            scheme,
+            authority,
+            path,
+            query,
+            fragment,
+        }
+    }
+}
+
+fn is_scheme(scheme: &str) -> bool {
+    let mut chars = scheme.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+/// Removes `.` and `..` segments from a path as described by RFC 3986 section 5.2.4.
+fn remove_dot_segments(path: &str) -> String {
+    let (absolute, relative) = match path.strip_prefix('/') {
+        Some(relative) => (true, relative),
+        None => (false, path),
+    };
+    let mut output = Vec::new();
+    let mut segments = relative.split('/').peekable();
+    while let Some(segment) = segments.next() {
+        let last = segments.peek().is_none();
+        match segment {
+            "." => {}
+            ".." => {
+                output.pop();
+            }
+            segment => {
+                output.push(segment);
+                continue;
+            }
+        }
+        if last {
+            output.push("");
+        }
+    }
+    let mut result = String::with_capacity(path.len());
+    if absolute {
+        result.push('/');
+    }
+    result.push_str(&output.join("/"));
+    result
+}
+
+/// Applies the `xml:base` of an element to the base URL in scope of it, returning the base
+/// URL for the content of the element.
+pub(crate) fn join_base(base: Option<&str>, own: Option<&str>) -> Option<String> {
+    match (base, own) {
+        (Some(base), Some(own)) => Some(resolve_uri(base, own)),
+        (None, Some(own)) => Some(own.to_string()),
+        (base, None) => base.map(ToString::to_string),
+    }
+}
+
+/// Resolves a URI reference against a base URI as described by RFC 3986 section 5.2.
+///
+/// References with a scheme, such as `mailto:` or `urn:` ones, are kept as they are apart from
+/// dot segments, and network-path references (`//host/path`) take the scheme of the base.
+pub(crate) fn resolve_uri(base: &str, reference: &str) -> String {
+    let base = UriRef::parse(base);
+    let reference = UriRef::parse(reference);
+
+    let (scheme, authority, path, query) = if reference.scheme.is_some() {
+        (
+            reference.scheme,
+            reference.authority,
+            remove_dot_segments(reference.path),
+            reference.query,
+        )
+    } else if reference.authority.is_some() {
+        (
+            base.scheme,
+            reference.authority,
+            remove_dot_segments(reference.path),
+            reference.query,
+        )
+    } else if reference.path.is_empty() {
+        (
+            base.scheme,
+            base.authority,
+            String::from(base.path),
+            reference.query.or(base.query),
+        )
+    } else if reference.path.starts_with('/') {
+        (
+            base.scheme,
+            base.authority,
+            remove_dot_segments(reference.path),
+            reference.query,
+        )
+    } else {
+        let merged = if base.authority.is_some() && base.path.is_empty() {
+            ["/", reference.path].concat()
+        } else {
+            let directory = base.path.rfind('/').map_or("", |i| &base.path[..=i]);
+            [directory, reference.path].concat()
+        };
+        (
+            base.scheme,
+            base.authority,
+            remove_dot_segments(&merged),
+            reference.query,
+        )
+    };
+
+    let mut result = String::new();
+    if let Some(scheme) = scheme {
+        result.push_str(scheme);
+        result.push(':');
+    }
+    if let Some(authority) = authority {
+        result.push_str("//");
+        result.push_str(authority);
+    }
+    result.push_str(&path);
+    if let Some(query) = query {
+        result.push('?');
+        result.push_str(query);
+    }
+    if let Some(fragment) = reference.fragment {
+        result.push('#');
+        result.push_str(fragment);
+    }
+    result
+}
+
+#[cfg(feature = "std")]
+/// Returns the current time in UTC, truncated to whole seconds.
+pub fn now() -> FixedDateTime {
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    FixedOffset::east_opt(0)
+        .and_then(|utc| utc.timestamp_opt(since_epoch.as_secs() as i64, 0).single())
+        .unwrap_or_else(default_fixed_datetime)
+}
+
+/// Parses an RFC 3339 timestamp as given to the `*_rfc3339` builder methods.
+#[cfg(feature = "builders")]
+pub(crate) fn parse_rfc3339(field: &'static str, value: &str) -> Result<FixedDateTime, Error> {
+    FixedDateTime::parse_from_rfc3339(value).map_err(|_| Error::WrongDatetime {
+        field,
+        entry: None,
+        value: value.to_string(),
+    })
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn decode<'s, B: BufRead>(
     bytes: &'s [u8],
-    reader: &'r Reader<B>,
+    reader: &Reader<B>,
 ) -> Result<Cow<'s, str>, Error> {
     let text = reader.decoder().decode(bytes).map_err(XmlError::new)?;
     Ok(text)
 }
 
-pub(crate) fn attr_value<'s, 'r, B: BufRead>(
+#[cfg(feature = "std")]
+pub(crate) fn attr_value<'s, B: BufRead>(
     attr: &'s Attribute<'s>,
-    reader: &'r Reader<B>,
+    reader: &Reader<B>,
+    opts: &ParseOpts,
 ) -> Result<Cow<'s, str>, Error> {
     let value = attr
-        .decode_and_unescape_value(reader.decoder())
+        .decode_and_unescape_value_with(reader.decoder(), |entity| resolve_entity(entity, opts))
         .map_err(XmlError::new)?;
     Ok(value)
 }
 
+#[cfg(feature = "std")]
+/// Resolves a named entity using the caller supplied table first, then the predefined XML ones
+/// and, if enabled, the HTML5 ones.
+pub(crate) fn resolve_entity<'e>(entity: &str, opts: &'e ParseOpts) -> Option<&'e str> {
+    opts.entities
+        .get(entity)
+        .map(String::as_str)
+        .or_else(|| resolve_xml_entity(entity))
+        .or_else(|| resolve_html_entity(entity, opts))
+}
+
+#[cfg(all(feature = "std", feature = "html-entities"))]
+fn resolve_html_entity(entity: &str, opts: &ParseOpts) -> Option<&'static str> {
+    if opts.html_entities {
+        quick_xml::escape::resolve_html5_entity(entity)
+    } else {
+        None
+    }
+}
+
+#[cfg(all(feature = "std", not(feature = "html-entities")))]
+fn resolve_html_entity(_entity: &str, _opts: &ParseOpts) -> Option<&'static str> {
+    None
+}
+
+#[cfg(feature = "std")]
+/// Fails with `Error::DuplicateElement` if `name` is one of `singletons` and has been seen
+/// before, when enabled by `opts`. Otherwise the name is returned unchanged.
+pub(crate) fn check_duplicate<'s>(
+    name: Cow<'s, str>,
+    singletons: &[&'static str],
+    seen: &mut Vec<&'static str>,
+    opts: &ParseOpts,
+) -> Result<Cow<'s, str>, Error> {
+    if opts.deny_duplicate_elements {
+        if let Some(&element) = singletons.iter().find(|&&element| element == name) {
+            if seen.contains(&element) {
+                return Err(Error::DuplicateElement { element });
+            }
+            seen.push(element);
+        }
+    }
+    Ok(name)
+}
+
+#[cfg(feature = "std")]
 pub(crate) fn skip<B: BufRead>(end: QName<'_>, reader: &mut Reader<B>) -> Result<(), Error> {
     reader
         .read_to_end_into(end, &mut Vec::new())
@@ -42,6 +284,7 @@ pub(crate) fn skip<B: BufRead>(end: QName<'_>, reader: &mut Reader<B>) -> Result
     Ok(())
 }
 
+#[cfg(feature = "std")]
 fn non_empty(string: String) -> Option<String> {
     if !string.is_empty() {
         Some(string)
@@ -50,71 +293,146 @@ fn non_empty(string: String) -> Option<String> {
     }
 }
 
-pub fn atom_text<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>, Error> {
-    reader.config_mut().expand_empty_elements = false;
+/// Accumulates the text of an element, keeping at most `limit` bytes of it.
+#[cfg(feature = "std")]
+struct TextBuf {
+    value: String,
+    limit: Option<usize>,
+    truncated: bool,
+    /// Whether the text is kept at all, which is not the case for an unused representation.
+    collect: bool,
+}
 
-    let mut innerbuf = Vec::new();
-    let mut depth = 0;
-    let mut result = String::new();
+#[cfg(feature = "std")]
+impl TextBuf {
+    fn new(limit: Option<usize>) -> Self {
+        TextBuf {
+            value: String::new(),
+            limit,
+            truncated: false,
+            collect: true,
+        }
+    }
 
-    loop {
-        match reader
-            .read_event_into(&mut innerbuf)
-            .map_err(XmlError::new)?
-        {
-            Event::Start(start) => {
-                depth += 1;
-                result.push('<');
-                result.push_str(decode(&start, reader)?.as_ref());
-                result.push('>');
-            }
-            Event::End(end) => {
-                if depth <= 0 {
-                    break;
+    /// Creates a buffer which drops all text pushed to it.
+    fn ignored() -> Self {
+        TextBuf {
+            collect: false,
+            ..TextBuf::new(None)
+        }
+    }
+
+    fn push(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Pushes text escaped for use as markup.
+    fn push_escaped(&mut self, string: &str) {
+        if self.collect {
+            self.push_str(escape(string).as_ref());
+        }
+    }
+
+    fn push_str(&mut self, string: &str) {
+        if self.truncated || !self.collect {
+            return;
+        }
+        match self.limit {
+            Some(limit) if self.value.len() + string.len() > limit => {
+                let mut end = limit - self.value.len();
+                while !string.is_char_boundary(end) {
+                    end -= 1;
                 }
-                depth -= 1;
-                result.push_str("</");
-                result.push_str(decode(end.name().as_ref(), reader)?.as_ref());
-                result.push('>');
-            }
-            Event::Empty(start) => {
-                result.push('<');
-                result.push_str(decode(&start, reader)?.as_ref());
-                result.push_str("/>");
-            }
-            Event::CData(text) => {
-                result.push_str(decode(&text, reader)?.as_ref());
-            }
-            Event::Text(text) => {
-                let decoded = text.unescape().map_err(XmlError::new)?;
-                result.push_str(&decoded);
+                self.value.push_str(&string[..end]);
+                self.truncated = true;
             }
-            Event::Comment(text) => {
-                let decoded = text.unescape().map_err(XmlError::new)?;
-                result.push_str("<!--");
-                result.push_str(&decoded);
-                result.push_str("-->");
-            }
-            Event::Decl(_decl) => {}
-            Event::PI(_text) => {}
-            Event::DocType(_text) => {}
-            Event::Eof => return Err(Error::Eof),
+            _ => self.value.push_str(string),
         }
+    }
 
-        innerbuf.clear();
+    /// Returns the text, if not empty, and whether it was truncated.
+    fn finish(self) -> (Option<String>, bool) {
+        (non_empty(self.value), self.truncated)
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn atom_text<B: BufRead>(
+    reader: &mut Reader<B>,
+    opts: &ParseOpts,
+) -> Result<Option<String>, Error> {
+    Ok(atom_text_limited(reader, opts, None)?.0)
+}
+
+/// Reads the text of an element like `atom_text`, or returns `None` without reading if the
+/// element is empty, e.g. `<icon/>`.
+#[cfg(feature = "std")]
+pub fn element_text<B: BufRead>(
+    reader: &mut Reader<B>,
+    empty: bool,
+    opts: &ParseOpts,
+) -> Result<Option<String>, Error> {
+    if empty {
+        Ok(None)
+    } else {
+        atom_text(reader, opts)
     }
+}
 
-    reader.config_mut().expand_empty_elements = true;
+/// Reads the text of an element like `atom_text`, keeping at most `limit` bytes of it. Also
+/// returns whether the text was truncated.
+#[cfg(feature = "std")]
+pub fn atom_text_limited<B: BufRead>(
+    reader: &mut Reader<B>,
+    opts: &ParseOpts,
+    limit: Option<usize>,
+) -> Result<(Option<String>, bool), Error> {
+    let body = read_body(reader, opts, TextBuf::new(limit), TextBuf::ignored())?;
+    Ok(body.text.finish())
+}
 
-    Ok(non_empty(result))
+/// Reads the markup of an element, keeping at most `limit` bytes of it. Also
+/// returns whether the markup was truncated, which may leave it unbalanced.
+///
+/// Tags are copied verbatim, including the escaping and quoting of their attribute values, while
+/// text is escaped again after resolving entities.
+#[cfg(feature = "std")]
+pub fn atom_xhtml_limited<B: BufRead>(
+    reader: &mut Reader<B>,
+    opts: &ParseOpts,
+    limit: Option<usize>,
+) -> Result<(Option<String>, bool), Error> {
+    let body = read_body(reader, opts, TextBuf::ignored(), TextBuf::new(limit))?;
+    Ok(body.xhtml.finish())
 }
 
-pub fn atom_xhtml<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>, Error> {
-    reader.config_mut().expand_empty_elements = false;
+/// The body of an element read as text, as markup or both.
+#[cfg(feature = "std")]
+struct Body {
+    /// The body read as text, with entities resolved and CDATA sections copied verbatim.
+    text: TextBuf,
+    /// The body read as markup, with text escaped again.
+    xhtml: TextBuf,
+    /// The number of elements found directly within the body.
+    roots: usize,
+    /// Whether non-whitespace text was found outside of these elements.
+    loose_text: bool,
+}
 
+/// Reads an element body into `text` as text and into `xhtml` as markup. This is the one loop
+/// behind the readers of element bodies, which pass `TextBuf::ignored()` for the
+/// representation they do not need.
+#[cfg(feature = "std")]
+fn read_body<B: BufRead>(
+    reader: &mut Reader<B>,
+    opts: &ParseOpts,
+    mut text: TextBuf,
+    mut xhtml: TextBuf,
+) -> Result<Body, Error> {
     let mut innerbuf = Vec::new();
     let mut depth = 0;
-    let mut result = String::new();
+    let mut roots = 0;
+    let mut loose_text = false;
 
     loop {
         match reader
@@ -122,37 +440,62 @@ pub fn atom_xhtml<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>,
             .map_err(XmlError::new)?
         {
             Event::Start(start) => {
+                if depth == 0 {
+                    roots += 1;
+                }
                 depth += 1;
-                result.push('<');
-                result.push_str(decode(&start, reader)?.as_ref());
-                result.push('>');
+                let start = decode(&start, reader)?;
+                for result in [&mut text, &mut xhtml] {
+                    result.push('<');
+                    result.push_str(start.as_ref());
+                    result.push('>');
+                }
             }
             Event::End(end) => {
                 if depth <= 0 {
                     break;
                 }
                 depth -= 1;
-                result.push_str("</");
-                result.push_str(decode(end.name().as_ref(), reader)?.as_ref());
-                result.push('>');
+                let name = end.name();
+                let end = decode(name.as_ref(), reader)?;
+                for result in [&mut text, &mut xhtml] {
+                    result.push_str("</");
+                    result.push_str(end.as_ref());
+                    result.push('>');
+                }
             }
             Event::Empty(start) => {
-                result.push('<');
-                result.push_str(decode(&start, reader)?.as_ref());
-                result.push_str("/>");
+                if depth == 0 {
+                    roots += 1;
+                }
+                let start = decode(&start, reader)?;
+                for result in [&mut text, &mut xhtml] {
+                    result.push('<');
+                    result.push_str(start.as_ref());
+                    result.push_str("/>");
+                }
             }
-            Event::CData(text) => {
-                result.push_str(escape(decode(&text, reader)?.as_ref()).as_ref());
+            Event::CData(cdata) => {
+                let decoded = decode(&cdata, reader)?;
+                loose_text |= depth == 0 && !decoded.trim().is_empty();
+                text.push_str(decoded.as_ref());
+                xhtml.push_escaped(decoded.as_ref());
             }
-            Event::Text(text) => {
-                let decoded = text.unescape().map_err(XmlError::new)?;
-                result.push_str(escape(decoded.as_ref()).as_ref());
+            Event::Text(event) => {
+                let decoded = event
+                    .unescape_with(|entity| resolve_entity(entity, opts))
+                    .map_err(XmlError::new)?;
+                loose_text |= depth == 0 && !decoded.trim().is_empty();
+                text.push_str(&decoded);
+                xhtml.push_escaped(decoded.as_ref());
             }
-            Event::Comment(text) => {
-                let decoded = text.unescape().map_err(XmlError::new)?;
-                result.push_str("<!--");
-                result.push_str(&decoded);
-                result.push_str("-->");
+            Event::Comment(comment) => {
+                let decoded = comment.unescape().map_err(XmlError::new)?;
+                for result in [&mut text, &mut xhtml] {
+                    result.push_str("<!--");
+                    result.push_str(&decoded);
+                    result.push_str("-->");
+                }
             }
             Event::Decl(_decl) => {}
             Event::PI(_text) => {}
@@ -163,15 +506,70 @@ pub fn atom_xhtml<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<String>,
         innerbuf.clear();
     }
 
-    reader.config_mut().expand_empty_elements = true;
+    Ok(Body {
+        text,
+        xhtml,
+        roots,
+        loose_text,
+    })
+}
 
-    Ok(non_empty(result))
+/// Reads an element body which lacks an explicit markup type, guessing whether it is xhtml.
+///
+/// The body is considered xhtml if it consists of a single wrapping element surrounded only by
+/// whitespace. Returns the value read according to the guess, keeping at most `limit` bytes of
+/// it, whether xhtml was guessed and whether the value was truncated.
+#[cfg(feature = "std")]
+pub fn atom_sniffed<B: BufRead>(
+    reader: &mut Reader<B>,
+    opts: &ParseOpts,
+    limit: Option<usize>,
+) -> Result<(Option<String>, bool, bool), Error> {
+    let body = read_body(reader, opts, TextBuf::new(limit), TextBuf::new(limit))?;
+    if body.roots == 1 && !body.loose_text {
+        Ok((
+            non_empty(body.xhtml.value.trim().to_string()),
+            true,
+            body.xhtml.truncated,
+        ))
+    } else {
+        let (text, truncated) = body.text.finish();
+        Ok((text, false, truncated))
+    }
+}
+
+/// Reads the body of `html` content which may contain child elements instead of escaped markup.
+///
+/// If the body contains elements it is read as markup like `atom_xhtml_limited`, so that the
+/// value stays well-formed. Otherwise it is read like `atom_text_limited`. Returns the value,
+/// keeping at most `limit` bytes of it, and whether it was truncated.
+#[cfg(feature = "std")]
+pub fn atom_html<B: BufRead>(
+    reader: &mut Reader<B>,
+    opts: &ParseOpts,
+    limit: Option<usize>,
+) -> Result<(Option<String>, bool), Error> {
+    let body = read_body(reader, opts, TextBuf::new(limit), TextBuf::new(limit))?;
+    if body.roots > 0 {
+        Ok(body.xhtml.finish())
+    } else {
+        Ok(body.text.finish())
+    }
 }
 
-pub fn atom_datetime<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<FixedDateTime>, Error> {
-    if let Some(datetime_text) = atom_text(reader)? {
+#[cfg(feature = "std")]
+pub fn atom_datetime<B: BufRead>(
+    reader: &mut Reader<B>,
+    field: &'static str,
+    opts: &ParseOpts,
+) -> Result<Option<FixedDateTime>, Error> {
+    if let Some(datetime_text) = atom_text(reader, opts)? {
         match diligent_date_parser::parse_date(&datetime_text) {
-            None => Err(Error::WrongDatetime(datetime_text)),
+            None => Err(Error::WrongDatetime {
+                field,
+                entry: None,
+                value: datetime_text,
+            }),
             Some(datetime) => Ok(Some(datetime)),
         }
     } else {
@@ -179,21 +577,219 @@ pub fn atom_datetime<B: BufRead>(reader: &mut Reader<B>) -> Result<Option<FixedD
     }
 }
 
-#[cfg(test)]
+/// Reads the date of an element like `atom_datetime`, or returns `None` without reading if the
+/// element is empty, e.g. `<updated/>`.
+#[cfg(feature = "std")]
+pub fn element_datetime<B: BufRead>(
+    reader: &mut Reader<B>,
+    empty: bool,
+    field: &'static str,
+    opts: &ParseOpts,
+) -> Result<Option<FixedDateTime>, Error> {
+    if empty {
+        Ok(None)
+    } else {
+        atom_datetime(reader, field, opts)
+    }
+}
+
+/// Formats a date as written to a feed, see `WriteConfig::preserve_subseconds`.
+#[cfg(feature = "std")]
+pub(crate) fn format_datetime(datetime: &FixedDateTime, config: &WriteConfig) -> String {
+    if config.preserve_subseconds {
+        datetime.to_rfc3339()
+    } else {
+        datetime.to_rfc3339_opts(SecondsFormat::Secs, false)
+    }
+}
+
+/// Decodes standard base64, ignoring whitespace. Returns `None` if the input is not valid.
+pub(crate) fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    fn value(c: u8) -> Option<u32> {
+        match c {
+            b'A'..=b'Z' => Some(u32::from(c - b'A')),
+            b'a'..=b'z' => Some(u32::from(c - b'a') + 26),
+            b'0'..=b'9' => Some(u32::from(c - b'0') + 52),
+            b'+' => Some(62),
+            b'/' => Some(63),
+            _ => None,
+        }
+    }
+
+    let chars = input
+        .bytes()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect::<Vec<_>>();
+    if chars.len() % 4 != 0 {
+        return None;
+    }
+    let mut output = Vec::with_capacity(chars.len() / 4 * 3);
+    let quads = chars.chunks(4).count();
+    for (i, quad) in chars.chunks(4).enumerate() {
+        let padding = quad.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 2 || (padding > 0 && i + 1 != quads) {
+            return None;
+        }
+        let mut bits = 0;
+        for &c in &quad[..4 - padding] {
+            bits = bits << 6 | value(c)?;
+        }
+        bits <<= 6 * padding as u32;
+        let bytes = bits.to_be_bytes();
+        output.extend_from_slice(&bytes[1..4 - padding]);
+    }
+    Some(output)
+}
+
+/// Query parameters commonly added to links for tracking, removed by `normalize_id`.
+pub(crate) const TRACKING_PARAMS: &[&str] = &[
+    "utm_source",
+    "utm_medium",
+    "utm_campaign",
+    "utm_term",
+    "utm_content",
+    "fbclid",
+    "gclid",
+];
+
+/// Normalizes an identifier for comparison.
+///
+/// Surrounding whitespace is trimmed and the scheme is lowercased. For hierarchical URIs the
+/// host is lowercased too and query parameters named in `tracking_params` are removed.
+pub(crate) fn normalize_id<'a>(id: &'a str, tracking_params: &[&str]) -> Cow<'a, str> {
+    let id = id.trim();
+    let uri = UriRef::parse(id);
+    let scheme = match uri.scheme {
+        Some(scheme) => scheme,
+        None => return Cow::Borrowed(id),
+    };
+
+    let mut result = String::with_capacity(id.len());
+    result.push_str(&scheme.to_ascii_lowercase());
+    result.push(':');
+    match uri.authority {
+        Some(authority) => {
+            result.push_str("//");
+            let (userinfo, host) = match authority.rfind('@') {
+                Some(i) => authority.split_at(i + 1),
+                None => ("", authority),
+            };
+            result.push_str(userinfo);
+            result.push_str(&host.to_ascii_lowercase());
+            result.push_str(uri.path);
+            if let Some(query) = uri.query {
+                let params = query
+                    .split('&')
+                    .filter(|param| {
+                        let name = param.split('=').next().unwrap_or_default();
+                        !tracking_params.contains(&name)
+                    })
+                    .collect::<Vec<_>>();
+                if !params.is_empty() {
+                    result.push('?');
+                    result.push_str(&params.join("&"));
+                }
+            }
+        }
+        None => {
+            result.push_str(uri.path);
+            if let Some(query) = uri.query {
+                result.push('?');
+                result.push_str(query);
+            }
+        }
+    }
+    if let Some(fragment) = uri.fragment {
+        result.push('#');
+        result.push_str(fragment);
+    }
+
+    if result == id {
+        Cow::Borrowed(id)
+    } else {
+        Cow::Owned(result)
+    }
+}
+
+/// Removes a second layer of escaping from HTML markup, as found in feeds whose generator
+/// escapes already escaped markup.
+///
+/// Markup which contains no `<` but an escaped `&lt;` is considered double-encoded. Then the
+/// predefined XML entities and character references are unescaped once. Returns `None` for
+/// markup which does not look double-encoded.
+pub(crate) fn decode_double_encoded(markup: &str) -> Option<String> {
+    if markup.contains('<') || !markup.contains("&lt;") {
+        return None;
+    }
+
+    let mut result = String::with_capacity(markup.len());
+    let mut rest = markup;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let reference = rest[1..].find(';').map(|end| &rest[1..end + 1]);
+        let c = match reference {
+            Some("lt") => Some('<'),
+            Some("gt") => Some('>'),
+            Some("amp") => Some('&'),
+            Some("quot") => Some('"'),
+            Some("apos") => Some('\''),
+            Some(reference) => match reference.strip_prefix('#') {
+                Some(code) => match code.strip_prefix('x').or_else(|| code.strip_prefix('X')) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => code.parse().ok(),
+                }
+                .and_then(char::from_u32),
+                None => None,
+            },
+            None => None,
+        };
+        match (c, reference) {
+            (Some(c), Some(reference)) => {
+                result.push(c);
+                rest = &rest[reference.len() + 2..];
+            }
+            _ => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    Some(result)
+}
+
+/// Computes the 64-bit FNV-1a hash of the given parts, each followed by a zero byte.
+///
+/// Unlike the hasher of the standard library the result is stable across releases and
+/// platforms, so it can be used for identifiers which are stored.
+pub(crate) fn stable_hash<'a, I: IntoIterator<Item = &'a str>>(parts: I) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    for part in parts {
+        for &byte in part.as_bytes().iter().chain(&[0]) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+#[cfg(all(test, feature = "std"))]
 mod test {
     use super::*;
     use crate::error::Error;
 
     fn read_x(xml: &str) -> Result<Option<String>, Error> {
         let mut reader = Reader::from_reader(xml.as_bytes());
-        reader.config_mut().expand_empty_elements = true;
         loop {
             let mut buf = Vec::new();
             match reader.read_event_into(&mut buf).map_err(XmlError::new)? {
                 Event::Start(element) => {
                     return match decode(element.name().as_ref(), &reader)? {
-                        Cow::Borrowed("text") => atom_text(&mut reader),
-                        Cow::Borrowed("raw") => atom_xhtml(&mut reader),
+                        Cow::Borrowed("text") => atom_text(&mut reader, &ParseOpts::default()),
+                        Cow::Borrowed("raw") => {
+                            Ok(atom_xhtml_limited(&mut reader, &ParseOpts::default(), None)?.0)
+                        }
                         _ => Err(Error::InvalidStartTag),
                     }
                 }
@@ -224,4 +820,111 @@ mod test {
             r#"<div>a line<br/>&amp; one more</div>"#
         );
     }
+
+    #[test]
+    fn test_resolve_uri() {
+        let base = "http://a/b/c/d;p?q";
+        let cases = [
+            // RFC 3986 section 5.4
+            ("g:h", "g:h"),
+            ("g", "http://a/b/c/g"),
+            ("./g", "http://a/b/c/g"),
+            ("g/", "http://a/b/c/g/"),
+            ("/g", "http://a/g"),
+            ("//g", "http://g"),
+            ("?y", "http://a/b/c/d;p?y"),
+            ("g?y", "http://a/b/c/g?y"),
+            ("#s", "http://a/b/c/d;p?q#s"),
+            ("g#s", "http://a/b/c/g#s"),
+            (";x", "http://a/b/c/;x"),
+            ("", "http://a/b/c/d;p?q"),
+            (".", "http://a/b/c/"),
+            ("./", "http://a/b/c/"),
+            ("..", "http://a/b/"),
+            ("../g", "http://a/b/g"),
+            ("../..", "http://a/"),
+            ("../../g", "http://a/g"),
+            ("../../../g", "http://a/g"),
+            ("/./g", "http://a/g"),
+            ("/../g", "http://a/g"),
+            ("g.", "http://a/b/c/g."),
+            ("..g", "http://a/b/c/..g"),
+            ("./../g", "http://a/b/g"),
+            ("./g/.", "http://a/b/c/g/"),
+            ("g/./h", "http://a/b/c/g/h"),
+            ("g/../h", "http://a/b/c/h"),
+            ("g;x=1/../y", "http://a/b/c/y"),
+            // absolute and protocol-relative references
+            ("https://example.com/x", "https://example.com/x"),
+            ("//example.com/x/../y", "http://example.com/y"),
+            ("mailto:john@example.com", "mailto:john@example.com"),
+            (
+                "urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6",
+                "urn:uuid:60a76c80-d399-11d9-b91C-0003939e0af6",
+            ),
+        ];
+        for (reference, expected) in cases {
+            assert_eq!(resolve_uri(base, reference), expected, "{}", reference);
+        }
+        assert_eq!(resolve_uri("http://a", "g"), "http://a/g");
+    }
+
+    #[test]
+    fn test_decode_double_encoded() {
+        assert_eq!(
+            decode_double_encoded("&lt;p&gt;Fish &amp;amp; chips&#33;&lt;/p&gt;").as_deref(),
+            Some("<p>Fish &amp; chips!</p>")
+        );
+        assert_eq!(
+            decode_double_encoded("&lt;b&gt;&nbsp;&#x4A;&unknown&lt;/b&gt;").as_deref(),
+            Some("<b>&nbsp;J&unknown</b>")
+        );
+        assert_eq!(decode_double_encoded("<p>&lt;tag&gt;</p>"), None);
+        assert_eq!(decode_double_encoded("Fish &amp; chips"), None);
+    }
+
+    #[test]
+    fn test_normalize_id() {
+        let cases = [
+            ("urn:uuid:1225c695", "urn:uuid:1225c695"),
+            ("  tag:example.com,2005:1  ", "tag:example.com,2005:1"),
+            ("TAG:example.com,2005:1", "tag:example.com,2005:1"),
+            ("HTTP://Example.COM/Post/1", "http://example.com/Post/1"),
+            ("http://User@Example.com/", "http://User@example.com/"),
+            (
+                "http://example.com/1?utm_source=rss&p=2&fbclid=x#top",
+                "http://example.com/1?p=2#top",
+            ),
+            (
+                "http://example.com/1?utm_medium=feed",
+                "http://example.com/1",
+            ),
+            ("not a uri", "not a uri"),
+        ];
+        for (id, expected) in cases {
+            assert_eq!(normalize_id(id, TRACKING_PARAMS), expected, "{}", id);
+        }
+        assert!(matches!(
+            normalize_id("http://example.com/1?a=b", TRACKING_PARAMS),
+            Cow::Borrowed(_)
+        ));
+        assert_eq!(
+            normalize_id("http://example.com/?ref=x&utm_source=y", &["ref"]),
+            "http://example.com/?utm_source=y"
+        );
+    }
+
+    #[test]
+    fn test_decode_base64() {
+        assert_eq!(decode_base64(""), Some(Vec::new()));
+        assert_eq!(decode_base64("SGVsbG8="), Some(b"Hello".to_vec()));
+        assert_eq!(decode_base64("SGVs\nbG8h"), Some(b"Hello!".to_vec()));
+        assert_eq!(decode_base64("SGk="), Some(b"Hi".to_vec()));
+        assert_eq!(decode_base64("SA=="), Some(b"H".to_vec()));
+        assert_eq!(decode_base64("+/+/"), Some(vec![0xfb, 0xff, 0xbf]));
+        assert_eq!(decode_base64("SGVsbG8"), None);
+        assert_eq!(decode_base64("SA==SA=="), None);
+        assert_eq!(decode_base64("S==="), None);
+        assert_eq!(decode_base64("SGV*"), None);
+    }
 }
@@ -160,3 +160,32 @@ fn test_builders() {
         )
     );
 }
+
+#[test]
+fn test_try_build_undeclared_namespace() {
+    let child = ExtensionBuilder::default().name("media:title").build();
+    let group = ExtensionBuilder::default()
+        .name("ext:group")
+        .ordered_child(child)
+        .build();
+    let mut extensions = BTreeMap::new();
+    extensions.insert("group".to_string(), vec![group]);
+    let entry = EntryBuilder::default()
+        .extension(("ext".to_string(), extensions))
+        .build();
+
+    let mut builder = FeedBuilder::default();
+    builder
+        .namespace(("ext".to_string(), "http://example.com".to_string()))
+        .entry(entry);
+    match builder.try_build() {
+        Err(Error::UndeclaredNamespace { prefix }) => assert_eq!(prefix, "media"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    builder.namespace((
+        "media".to_string(),
+        "http://search.yahoo.com/mrss/".to_string(),
+    ));
+    assert!(builder.try_build().is_ok());
+}
@@ -1,9 +1,11 @@
+#![cfg(feature = "std")]
+
 extern crate atom_syndication as atom;
 
 use std::fs::File;
 use std::io::BufReader;
 
-use crate::atom::Feed;
+use crate::atom::{DisplayBody, Feed};
 
 macro_rules! feed {
     ($f:expr) => {{
@@ -64,6 +66,7 @@ fn content_text_other() {
     let content = feed.entries().first().unwrap().content().unwrap();
     assert_eq!(content.value(), Some("RW50cnkgY29udGVudA=="));
     assert_eq!(content.content_type(), Some("application/octet-stream"));
+    assert!(content.is_binary());
 }
 
 #[test]
@@ -94,3 +97,173 @@ fn content_text_xhtml() {
     );
     assert_eq!(content.content_type(), Some("xhtml"));
 }
+
+#[test]
+fn content_display_body() {
+    let feed = feed!("tests/data/content_src.xml");
+    assert_eq!(
+        feed.entries()[0].display_body(),
+        Some(DisplayBody::ExternalLink(
+            "http://example.com/image.png".to_string()
+        ))
+    );
+
+    let feed = feed!("tests/data/content_text_plain.xml");
+    assert_eq!(
+        feed.entries()[0].display_body(),
+        Some(DisplayBody::Text("Entry content".to_string()))
+    );
+
+    let feed = feed!("tests/data/content_text_html.xml");
+    assert!(matches!(
+        feed.entries()[0].display_body(),
+        Some(DisplayBody::Html(_))
+    ));
+
+    let feed = feed!("tests/data/content_text_other.xml");
+    assert_eq!(feed.entries()[0].display_body(), None);
+
+    let xml = r#"<feed><entry><content type="text/html; charset=utf-8">&lt;p&gt;A&lt;/p&gt;</content></entry>
+        <entry><content type="application/xml">&lt;a/&gt;</content></entry></feed>"#;
+    let feed = xml.parse::<Feed>().unwrap();
+    assert_eq!(
+        feed.entries()[0].display_body(),
+        Some(DisplayBody::Html("<p>A</p>".to_string()))
+    );
+    assert_eq!(
+        feed.entries()[1].display_body(),
+        Some(DisplayBody::Text("<a/>".to_string()))
+    );
+}
+
+#[test]
+fn content_max_len() {
+    let xml = r#"<feed><entry>
+        <title>A long title which is not truncated</title>
+        <summary>Grüße aus Köln</summary>
+        <content type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml">Hello <b>World</b></div></content>
+    </entry><entry><summary>Short</summary><content>Short</content></entry></feed>"#;
+    let opts = atom::ParseOpts {
+        max_content_len: Some(4),
+        ..Default::default()
+    };
+    let feed = Feed::read_from_opts(xml.as_bytes(), &opts).unwrap();
+
+    let entry = &feed.entries()[0];
+    assert_eq!(entry.title().value, "A long title which is not truncated");
+    assert!(!entry.title().truncated);
+    let summary = entry.summary().unwrap();
+    assert_eq!(summary.value, "Grü");
+    assert!(summary.truncated);
+    let content = entry.content().unwrap();
+    assert_eq!(content.value(), Some("<div"));
+    assert!(content.truncated);
+
+    let entry = &feed.entries()[1];
+    assert_eq!(entry.summary().unwrap().value, "Shor");
+    assert!(entry.summary().unwrap().truncated);
+
+    let opts = atom::ParseOpts {
+        max_content_len: Some(5),
+        ..Default::default()
+    };
+    let feed = Feed::read_from_opts(xml.as_bytes(), &opts).unwrap();
+    // "ß" would not fit completely
+    assert_eq!(feed.entries()[0].summary().unwrap().value, "Grü");
+    let entry = &feed.entries()[1];
+    assert_eq!(entry.content().unwrap().value(), Some("Short"));
+    assert!(!entry.content().unwrap().truncated);
+}
+
+#[test]
+fn content_mixed() {
+    let xml = r#"<feed><entry>
+        <content type="text">a &amp; <b>b</b></content>
+    </entry><entry>
+        <content type="html">a &amp; <b>b</b> &lt;i&gt;c&lt;/i&gt;</content>
+    </entry><entry>
+        <content type="html">a &amp; &lt;i&gt;c&lt;/i&gt;</content>
+    </entry><entry>
+        <content type="xhtml"><div>a &amp; <b>b</b></div></content>
+    </entry><entry>
+        <content type="text/plain">a &amp; <b>b</b></content>
+    </entry></feed>"#;
+    let values = |opts: &atom::ParseOpts| {
+        let feed = Feed::read_from_opts(xml.as_bytes(), opts).unwrap();
+        feed.entries()
+            .iter()
+            .map(|entry| {
+                let content = entry.content().unwrap();
+                (
+                    content.content_type().unwrap().to_string(),
+                    content.value().unwrap().to_string(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let expected = [
+        ("text", "a & <b>b</b>"),
+        ("html", "a & <b>b</b> <i>c</i>"),
+        ("html", "a & <i>c</i>"),
+        ("xhtml", "<div>a &amp; <b>b</b></div>"),
+        ("text/plain", "a & <b>b</b>"),
+    ];
+    let expected = expected
+        .iter()
+        .map(|(t, v)| (t.to_string(), v.to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(values(&atom::ParseOpts::default()), expected);
+
+    let opts = atom::ParseOpts {
+        html_elements_as_xhtml: true,
+        ..Default::default()
+    };
+    let mut lenient = expected;
+    lenient[1].1 = "a &amp; <b>b</b> &lt;i&gt;c&lt;/i&gt;".to_string();
+    assert_eq!(values(&opts), lenient);
+}
+
+#[test]
+fn content_markup_attributes_roundtrip() {
+    let xml = r#"<feed><entry>
+        <content type="xhtml"><div><a href="x&amp;y" title='a "b"'>z</a><br class="c&lt;d"/></div></content>
+    </entry><entry>
+        <content type="text">a <a href="x&amp;y">z</a></content>
+    </entry></feed>"#;
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    let xhtml = feed.entries()[0].content().unwrap().value().unwrap();
+    assert_eq!(
+        xhtml,
+        r#"<div><a href="x&amp;y" title='a "b"'>z</a><br class="c&lt;d"/></div>"#
+    );
+
+    let text = feed.entries()[1].content().unwrap().value().unwrap();
+    assert_eq!(text, r#"a <a href="x&amp;y">z</a>"#);
+
+    let written = feed.to_string();
+    let reread = Feed::read_from(written.as_bytes()).unwrap();
+    assert_eq!(reread, feed);
+    let reread = Feed::read_from(reread.to_string().as_bytes()).unwrap();
+    assert_eq!(reread, feed);
+}
+
+#[test]
+fn content_double_encoded() {
+    let xml = r#"<feed><entry>
+        <summary type="html">&amp;lt;b&amp;gt;Fish &amp;amp;amp; chips&amp;lt;/b&amp;gt;</summary>
+        <content type="html">&amp;lt;p&amp;gt;Hello&amp;lt;/p&amp;gt;</content>
+    </entry></feed>"#;
+    let mut feed = Feed::read_from(xml.as_bytes()).unwrap();
+    let entry = &mut feed.entries_mut()[0];
+
+    let summary = entry.summary.as_mut().unwrap();
+    assert_eq!(summary.value, "&lt;b&gt;Fish &amp;amp; chips&lt;/b&gt;");
+    assert!(summary.decode_once_more());
+    assert_eq!(summary.value, "<b>Fish &amp; chips</b>");
+
+    let content = entry.content.as_mut().unwrap();
+    assert!(content.decode_once_more());
+    assert_eq!(content.value(), Some("<p>Hello</p>"));
+    assert!(!content.decode_once_more());
+}
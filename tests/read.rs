@@ -1,5 +1,8 @@
+#![cfg(feature = "std")]
+
 extern crate atom_syndication as atom;
 
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::BufReader;
 
@@ -88,6 +91,12 @@ fn read_category() {
     assert_eq!(category.term(), "technology");
     assert_eq!(category.scheme(), Some("http://example.com/scheme"));
     assert_eq!(category.label(), Some("Technology"));
+    assert_eq!(category.base(), None);
+
+    let category = feed.categories().get(1).unwrap();
+    assert_eq!(category.term(), "podcast");
+    assert_eq!(category.scheme(), Some("schemes/media"));
+    assert_eq!(category.base(), Some("http://example.com/"));
 }
 
 #[test]
@@ -99,6 +108,15 @@ fn read_generator() {
     assert_eq!(generator.version(), Some("1.0"));
 }
 
+#[test]
+fn read_generator_empty() {
+    let feed = feed!("tests/data/generator_empty.xml");
+    let generator = feed.generator().unwrap();
+    assert_eq!(generator.value(), "");
+    assert_eq!(generator.uri(), Some("http://example.com/generator"));
+    assert_eq!(generator.version(), Some("1.0"));
+}
+
 #[test]
 fn read_link() {
     let feed = feed!("tests/data/link.xml");
@@ -109,6 +127,12 @@ fn read_link() {
     assert_eq!(link.mime_type(), Some("audio/mpeg"));
     assert_eq!(link.title(), Some("audio"));
     assert_eq!(link.length(), Some("1000"));
+    assert_eq!(link.base(), None);
+
+    let link = feed.links().get(1).unwrap();
+    assert_eq!(link.rel(), "related");
+    assert_eq!(link.href(), "episode/1");
+    assert_eq!(link.base(), Some("http://example.com/podcast/"));
 }
 
 #[test]
@@ -151,13 +175,13 @@ fn read_extension() {
         Some("http://example.com")
     );
 
-    let check_extensions = |extensions: &ExtensionMap| {
+    let check_extensions = |extensions: &ExtensionMap, title_value: &str| {
         assert!(extensions.contains_key("ext"));
         let map = extensions.get("ext").unwrap();
 
         assert!(map.contains_key("title"));
         let title = map.get("title").unwrap().first().unwrap();
-        assert_eq!(title.value(), Some("<strong>Title</strong>"));
+        assert_eq!(title.value(), Some(title_value));
         assert_eq!(title.attrs().get("type").map(String::as_str), Some("text"));
 
         assert!(map.contains_key("parent"));
@@ -168,8 +192,22 @@ fn read_extension() {
         assert_eq!(child.value(), Some("Child"));
     };
 
-    check_extensions(feed.extensions());
-    check_extensions(entry.extensions());
+    check_extensions(feed.extensions(), "<strong>Title</strong>");
+    check_extensions(entry.extensions(), " <strong>Title</strong>  ");
+    assert_eq!(
+        entry.extensions()["ext"]["parent"][0].value(),
+        None,
+        "whitespace around children is not a value"
+    );
+
+    let opts = atom::ParseOpts {
+        trim_whitespace: true,
+        ..Default::default()
+    };
+    let file = File::open("tests/data/extension.xml").unwrap();
+    let feed = Feed::read_from_opts(BufReader::new(file), &opts).unwrap();
+    let title = &feed.entries()[0].extensions()["ext"]["title"][0];
+    assert_eq!(title.value(), Some("<strong>Title</strong>"));
 }
 
 #[test]
@@ -178,6 +216,14 @@ fn read_eof() {
     assert!(matches!(result, Err(Error::Eof)));
 }
 
+#[test]
+fn read_str_with_bom() {
+    let feed = "\u{feff}<?xml version=\"1.0\"?><feed><id>urn:feed</id></feed>"
+        .parse::<Feed>()
+        .unwrap();
+    assert_eq!(feed.id(), "urn:feed");
+}
+
 #[test]
 fn read_invalid_start() {
     let result = Feed::read_from("<wrong></wrong>".as_bytes());
@@ -316,3 +362,632 @@ fn generator_invalid_version() {
     let result = Feed::read_from("<feed><generator version=\"&;\"></generator></feed>".as_bytes());
     assert!(matches!(result, Err(Error::Xml(_))));
 }
+
+#[test]
+fn read_custom_entities() {
+    let mut entities = HashMap::new();
+    entities.insert("nbsp".to_string(), "\u{a0}".to_string());
+    entities.insert("company".to_string(), "Example & Co".to_string());
+
+    let xml = r#"<feed>
+        <title>Feed&nbsp;Title</title>
+        <rights>&copy; &company;</rights>
+        <link href="http://example.com/?a=1&amp;b=&company;"/>
+    </feed>"#;
+    let result = Feed::read_from(xml.as_bytes());
+    assert!(matches!(result, Err(Error::Xml(_))));
+
+    entities.insert("copy".to_string(), "\u{a9}".to_string());
+    let feed = Feed::read_from_with_entities(xml.as_bytes(), &entities).unwrap();
+    assert_eq!(feed.title(), "Feed\u{a0}Title");
+    assert_eq!(feed.rights().map(Text::as_str), Some("\u{a9} Example & Co"));
+    assert_eq!(
+        feed.links().first().map(|link| link.href()),
+        Some("http://example.com/?a=1&b=Example & Co")
+    );
+}
+
+#[cfg(feature = "html-entities")]
+#[test]
+fn read_html_entities() {
+    let xml = r#"<feed><title>Feed&nbsp;Title &copy; &custom;</title></feed>"#;
+    let result = Feed::read_from(xml.as_bytes());
+    assert!(matches!(result, Err(Error::Xml(_))));
+
+    let mut opts = atom::ParseOpts {
+        html_entities: true,
+        ..Default::default()
+    };
+    opts.entities
+        .insert("custom".to_string(), "Custom".to_string());
+    let feed = Feed::read_from_opts(xml.as_bytes(), &opts).unwrap();
+    assert_eq!(feed.title(), "Feed\u{a0}Title \u{a9} Custom");
+}
+
+#[cfg(not(feature = "html-entities"))]
+#[test]
+fn read_html_entities_ignored() {
+    let xml = r#"<feed><title>Feed&nbsp;Title</title></feed>"#;
+    let opts = atom::ParseOpts {
+        html_entities: true,
+        ..Default::default()
+    };
+    let result = Feed::read_from_opts(xml.as_bytes(), &opts);
+    assert!(matches!(result, Err(Error::Xml(_))));
+}
+
+#[test]
+fn read_missing_updated() {
+    let xml = r#"<feed><updated>2017-06-03T15:15:44-05:00</updated><entry><updated></updated></entry></feed>"#;
+
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    assert_eq!(feed.entries()[0].updated().timestamp(), 0);
+
+    let opts = atom::ParseOpts {
+        missing_updated: atom::MissingDate::Now,
+        ..Default::default()
+    };
+    let feed = Feed::read_from_opts(xml.as_bytes(), &opts).unwrap();
+    assert_eq!(feed.updated().to_rfc3339(), "2017-06-03T15:15:44-05:00");
+    assert!(feed.entries()[0].updated().timestamp() > 0);
+
+    let opts = atom::ParseOpts {
+        missing_updated: atom::MissingDate::Error,
+        ..Default::default()
+    };
+    assert!(matches!(
+        Feed::read_from_opts(xml.as_bytes(), &opts),
+        Err(Error::MissingElement { element: "updated" })
+    ));
+}
+
+#[test]
+fn read_missing_href() {
+    let xml =
+        r#"<feed><link rel="self"/><link href=""/><entry><link title="a"></link></entry></feed>"#;
+
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    assert_eq!(feed.links().len(), 2);
+    assert_eq!(feed.links()[0].href(), "");
+    assert_eq!(feed.links()[0].rel(), "self");
+    assert_eq!(feed.entries()[0].links()[0].title(), Some("a"));
+
+    let opts = atom::ParseOpts {
+        missing_href: atom::MissingHref::Skip,
+        ..Default::default()
+    };
+    let feed = Feed::read_from_opts(xml.as_bytes(), &opts).unwrap();
+    assert_eq!(feed.links().len(), 1);
+    assert_eq!(feed.links()[0].rel(), "alternate");
+    assert!(feed.entries()[0].links().is_empty());
+
+    let opts = atom::ParseOpts {
+        missing_href: atom::MissingHref::Error,
+        ..Default::default()
+    };
+    assert!(matches!(
+        Feed::read_from_opts(xml.as_bytes(), &opts),
+        Err(Error::MissingAttribute {
+            element: "link",
+            attribute: "href"
+        })
+    ));
+}
+
+#[test]
+fn read_wrong_datetime() {
+    let xml = r#"<feed><updated>2024-01-01T00:00:00Z</updated><entry></entry><entry><published>tomorrow</published></entry></feed>"#;
+    let err = Feed::read_from(xml.as_bytes()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "timestamps must be formatted by RFC3339, rather than tomorrow in published of entry 1"
+    );
+    match err {
+        Error::WrongDatetime {
+            field,
+            entry,
+            value,
+        } => {
+            assert_eq!(field, "published");
+            assert_eq!(entry, Some(1));
+            assert_eq!(value, "tomorrow");
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+
+    let xml = r#"<feed><updated>now</updated></feed>"#;
+    assert!(matches!(
+        Feed::read_from(xml.as_bytes()),
+        Err(Error::WrongDatetime {
+            field: "updated",
+            entry: None,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn read_missing_id() {
+    let xml = r#"<feed><entry><link href="http://example.com/1"/></entry><entry><title>Title</title></entry><entry><id>urn:a</id></entry></feed>"#;
+
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    assert_eq!(feed.entries()[0].id(), "");
+    assert_eq!(feed.entries()[1].id(), "");
+
+    let opts = atom::ParseOpts {
+        missing_id: atom::MissingId::Synthesize,
+        ..Default::default()
+    };
+    let feed = Feed::read_from_opts(xml.as_bytes(), &opts).unwrap();
+    assert_eq!(feed.entries()[0].id(), "http://example.com/1");
+    assert!(feed.entries()[1].id().starts_with("urn:hash:"));
+    assert_eq!(feed.entries()[2].id(), "urn:a");
+    let reread = Feed::read_from_opts(xml.as_bytes(), &opts).unwrap();
+    assert_eq!(reread.entries()[1].id(), feed.entries()[1].id());
+
+    let opts = atom::ParseOpts {
+        missing_id: atom::MissingId::Error,
+        ..Default::default()
+    };
+    assert!(matches!(
+        Feed::read_from_opts(xml.as_bytes(), &opts),
+        Err(Error::MissingElement { element: "id" })
+    ));
+}
+
+#[test]
+fn read_duplicate_elements() {
+    let xml =
+        r#"<feed><id>urn:a</id><entry><id>urn:b</id><id>urn:c</id></entry><id>urn:d</id></feed>"#;
+
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    assert_eq!(feed.id(), "urn:d");
+    assert_eq!(feed.entries()[0].id(), "urn:c");
+
+    let opts = atom::ParseOpts {
+        deny_duplicate_elements: true,
+        ..Default::default()
+    };
+    assert!(matches!(
+        Feed::read_from_opts(xml.as_bytes(), &opts),
+        Err(Error::DuplicateElement { element: "id" })
+    ));
+
+    let xml =
+        r#"<feed><id>urn:a</id><entry><id>urn:b</id></entry><entry><id>urn:c</id></entry></feed>"#;
+    let feed = Feed::read_from_opts(xml.as_bytes(), &opts).unwrap();
+    assert_eq!(feed.entries().len(), 2);
+}
+
+#[test]
+fn read_invalid_extension_names() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:a="urn:a">
+        <a:b:c>one</a:b:c>
+        <a:>two</a:>
+        <:foo>three</:foo>
+        <a:ok>four</a:ok>
+    </feed>"#;
+    let feed = xml.parse::<Feed>().unwrap();
+    let map = feed.extensions().get("a").unwrap();
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.get("ok").unwrap()[0].value(), Some("four"));
+
+    let mut bytes = b"<feed><a:".to_vec();
+    bytes.extend_from_slice(&[0xff, 0xfe]);
+    bytes.extend_from_slice(b">x</a:");
+    bytes.extend_from_slice(&[0xff, 0xfe]);
+    bytes.extend_from_slice(b"></feed>");
+    assert!(matches!(Feed::read_from(&bytes[..]), Err(Error::Xml(_))));
+}
+
+#[test]
+fn read_not_atom() {
+    let rss = r#"<?xml version="1.0"?><rss version="2.0"><channel></channel></rss>"#;
+    match rss.parse::<Feed>() {
+        Err(Error::NotAtom { root }) => assert_eq!(root, "rss"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let rdf = r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"></rdf:RDF>"#;
+    match rdf.parse::<Feed>() {
+        Err(Error::NotAtom { root }) => assert_eq!(root, "rdf:RDF"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    assert!(matches!(
+        "<html></html>".parse::<Feed>(),
+        Err(Error::InvalidStartTag)
+    ));
+}
+
+#[test]
+fn read_wrong_namespace() {
+    let opts = atom::ParseOpts {
+        require_atom_namespace: true,
+        ..Default::default()
+    };
+
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom"><title>Feed Title</title></feed>"#;
+    let feed = Feed::read_from_opts(xml.as_bytes(), &opts).unwrap();
+    assert_eq!(feed.title(), "Feed Title");
+
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/atom"><title>Feed Title</title></feed>"#;
+    assert!(Feed::read_from(xml.as_bytes()).is_ok());
+    match Feed::read_from_opts(xml.as_bytes(), &opts) {
+        Err(Error::WrongNamespace { found }) => {
+            assert_eq!(found.as_deref(), Some("http://www.w3.org/2005/atom"))
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    assert!(matches!(
+        Feed::read_from_opts("<feed/>".as_bytes(), &opts),
+        Err(Error::WrongNamespace { found: None })
+    ));
+}
+
+#[test]
+fn read_ordered_extension_children() {
+    let xml = r#"<feed xmlns:ext="urn:ext"><ext:list><ext:b>1</ext:b><ext:a>2</ext:a><ext:b>3</ext:b></ext:list></feed>"#;
+
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    let list = &feed.extensions()["ext"]["list"][0];
+    assert_eq!(list.children()["b"].len(), 2);
+    assert!(list.ordered_children().is_empty());
+
+    let opts = atom::ParseOpts {
+        ordered_extension_children: true,
+        ..Default::default()
+    };
+    let feed = Feed::read_from_opts(xml.as_bytes(), &opts).unwrap();
+    let list = &feed.extensions()["ext"]["list"][0];
+    assert!(list.children().is_empty());
+    let values = list
+        .ordered_children()
+        .iter()
+        .map(|child| (child.name(), child.value().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(values, vec![("ext:b", "1"), ("ext:a", "2"), ("ext:b", "3")]);
+    assert!(feed
+        .to_string()
+        .contains("<ext:list><ext:b>1</ext:b><ext:a>2</ext:a><ext:b>3</ext:b></ext:list>"));
+}
+
+#[test]
+fn read_extension_depth_limit() {
+    let nested = |depth: usize| {
+        format!(
+            r#"<feed xmlns:ext="urn:ext">{}{}</feed>"#,
+            "<ext:a>".repeat(depth),
+            "</ext:a>".repeat(depth)
+        )
+    };
+
+    let feed = Feed::read_from(nested(64).as_bytes()).unwrap();
+    assert_eq!(feed.extensions()["ext"]["a"].len(), 1);
+    assert!(matches!(
+        Feed::read_from(nested(65).as_bytes()),
+        Err(Error::LimitExceeded {
+            option: "max_extension_depth",
+            limit: 64
+        })
+    ));
+    assert!(matches!(
+        Feed::read_from(nested(100_000).as_bytes()),
+        Err(Error::LimitExceeded { .. })
+    ));
+
+    let opts = atom::ParseOpts {
+        max_extension_depth: None,
+        ..Default::default()
+    };
+    assert!(Feed::read_from_opts(nested(65).as_bytes(), &opts).is_ok());
+}
+
+#[test]
+fn read_filtered() {
+    let xml = r#"<feed><entry><id>urn:a</id></entry><entry><id>urn:b</id></entry><entry><id>urn:c</id></entry></feed>"#;
+    let mut seen = Vec::new();
+    let feed = Feed::read_from_filtered(xml.as_bytes(), |entry| {
+        seen.push(entry.id().to_string());
+        entry.id() != "urn:b"
+    })
+    .unwrap();
+    assert_eq!(seen, vec!["urn:a", "urn:b", "urn:c"]);
+    let ids = feed.entries().iter().map(|e| e.id()).collect::<Vec<_>>();
+    assert_eq!(ids, vec!["urn:a", "urn:c"]);
+
+    let feed = Feed::read_from_filtered("<feed/>".as_bytes(), |_| false).unwrap();
+    assert!(feed.entries().is_empty());
+}
+
+#[test]
+fn read_nested_feed() {
+    let xml = r#"<?xml version="1.0"?>
+<soap:Envelope xmlns:soap="http://www.w3.org/2003/05/soap-envelope">
+    <soap:Body>
+        <title>Not the feed title</title>
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <title>Feed Title</title>
+            <entry><title>Entry Title</title></entry>
+        </feed>
+    </soap:Body>
+</soap:Envelope>"#;
+    assert!(matches!(
+        Feed::read_from(xml.as_bytes()),
+        Err(Error::InvalidStartTag)
+    ));
+
+    let feed = Feed::find_and_read(xml.as_bytes()).unwrap();
+    assert_eq!(feed.title(), "Feed Title");
+    assert_eq!(feed.entries().len(), 1);
+
+    assert!(matches!(
+        Feed::find_and_read("<envelope></envelope>".as_bytes()),
+        Err(Error::Eof)
+    ));
+
+    let opts = atom::ParseOpts {
+        deny_duplicate_elements: true,
+        ..Default::default()
+    };
+    let xml = "<envelope><feed><id>urn:a</id><id>urn:b</id></feed></envelope>";
+    assert_eq!(Feed::find_and_read(xml.as_bytes()).unwrap().id(), "urn:b");
+    assert!(matches!(
+        Feed::find_and_read_opts(xml.as_bytes(), &opts),
+        Err(Error::DuplicateElement { element: "id" })
+    ));
+}
+
+#[test]
+fn read_effective_authors() {
+    let feed = feed!("tests/data/source.xml");
+    let entry = feed.entries().first().unwrap();
+    assert!(entry.authors().is_empty());
+    let names = entry
+        .effective_authors(&feed)
+        .iter()
+        .map(|person| person.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["John Doe", "Jane Doe"]);
+}
+
+#[test]
+fn read_empty_elements() {
+    let xml = r#"<feed>
+        <title type="html"/>
+        <link href="http://example.com/a"/>
+        <link href="http://example.com/b"></link>
+        <category term="a"/>
+        <category term="b"></category>
+        <author/>
+        <author><name>John</name><email/></author>
+        <generator uri="http://example.com/generator"/>
+        <icon/>
+        <updated/>
+        <ext:a xmlns:ext="http://example.com/ext" x="1"/>
+        <ext:a xmlns:ext="http://example.com/ext"><ext:b/><ext:b>2</ext:b></ext:a>
+        <unknown/>
+        <entry/>
+        <entry><id>urn:b</id><summary/><content type="xhtml"><div>a<br/>b</div></content></entry>
+    </feed>"#;
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+
+    assert_eq!(feed.title().value, "");
+    assert_eq!(feed.title().r#type, atom::TextType::Html);
+    let hrefs = feed.links().iter().map(|l| l.href()).collect::<Vec<_>>();
+    assert_eq!(hrefs, vec!["http://example.com/a", "http://example.com/b"]);
+    let terms = feed
+        .categories()
+        .iter()
+        .map(|c| c.term())
+        .collect::<Vec<_>>();
+    assert_eq!(terms, vec!["a", "b"]);
+    assert_eq!(feed.authors().len(), 2);
+    assert_eq!(feed.authors()[1].name(), "John");
+    assert_eq!(feed.authors()[1].email(), None);
+    let generator = feed.generator().unwrap();
+    assert_eq!(generator.uri(), Some("http://example.com/generator"));
+    assert_eq!(generator.value(), "");
+    assert_eq!(feed.icon(), None);
+
+    let exts = &feed.extensions()["ext"]["a"];
+    assert_eq!(exts.len(), 2);
+    assert_eq!(exts[0].name(), "ext:a");
+    assert_eq!(exts[0].attrs()["x"], "1");
+    let children = &exts[1].children()["b"];
+    assert_eq!(children[0].value(), None);
+    assert_eq!(children[1].value(), Some("2"));
+
+    assert_eq!(feed.entries().len(), 2);
+    let entry = &feed.entries()[1];
+    assert_eq!(entry.id(), "urn:b");
+    assert_eq!(entry.summary().map(|s| s.as_str()), Some(""));
+    assert_eq!(
+        entry.content().and_then(|c| c.value()),
+        Some("<div>a<br/>b</div>")
+    );
+
+    let feed = Feed::read_from(r#"<feed xml:lang="en"/>"#.as_bytes()).unwrap();
+    assert_eq!(feed.lang(), Some("en"));
+}
+
+#[test]
+fn read_from_chunks() {
+    let xml = std::fs::read("tests/data/feed.xml").unwrap();
+    let expected = Feed::read_from(xml.as_slice()).unwrap();
+
+    for size in [1, 7, xml.len()] {
+        let mut parser = atom::FeedParser::new();
+        for chunk in xml.chunks(size) {
+            parser.feed_bytes(chunk).unwrap();
+        }
+        assert_eq!(parser.len(), xml.len());
+        assert_eq!(parser.entries_parsed(), expected.entries().len());
+        assert_eq!(parser.finish().unwrap(), expected);
+    }
+
+    let mut parser = atom::FeedParser::new();
+    parser.feed_bytes(&xml[..xml.len() / 2]).unwrap();
+    assert!(parser.finish().is_err());
+
+    let xml = r#"<feed xml:base="http://example.com/"><entry><id>urn:a</id><link href="a"/></entry>
+        <entry><updated>yesterday</updated></entry><title>Feed</title></feed>"#;
+    let mut parser = atom::FeedParser::new();
+    let split = xml.find("<entry><updated>").unwrap();
+    parser.feed_bytes(&xml.as_bytes()[..split]).unwrap();
+    assert_eq!(parser.entries_parsed(), 1);
+    assert!(matches!(
+        parser.feed_bytes(&xml.as_bytes()[split..]),
+        Err(Error::WrongDatetime { entry: Some(1), .. })
+    ));
+
+    let xml = xml.replace("yesterday", "2024-01-01T00:00:00Z");
+    let mut parser = atom::FeedParser::new();
+    for chunk in xml.as_bytes().chunks(3) {
+        parser.feed_bytes(chunk).unwrap();
+    }
+    let feed = parser.finish().unwrap();
+    assert_eq!(feed, Feed::read_from(xml.as_bytes()).unwrap());
+    assert_eq!(
+        feed.entries()[0].links()[0].resolved_href(None),
+        "http://example.com/a"
+    );
+
+    let latin1 = b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?><feed><entry><title>Caf\xe9</title></entry></feed>";
+    let mut parser = atom::FeedParser::new();
+    for chunk in latin1.chunks(5) {
+        parser.feed_bytes(chunk).unwrap();
+    }
+    assert_eq!(parser.entries_parsed(), 0);
+    assert_eq!(parser.finish().unwrap().entries()[0].title(), "Caf\u{e9}");
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn read_from_parallel() {
+    let mut xml = String::from(
+        r#"<?xml version="1.0"?><feed xmlns:dc="http://purl.org/dc/elements/1.1/"><title>Feed</title>"#,
+    );
+    for i in 0..100 {
+        xml.push_str(&format!(
+            r#"<entry><id>urn:{}</id><dc:creator>A</dc:creator><content type="xhtml"><div><entry/></div></content></entry>"#,
+            i
+        ));
+    }
+    xml.push_str("<entry/><id>urn:feed</id></feed>");
+
+    let feed = Feed::read_from_parallel(xml.as_bytes()).unwrap();
+    assert_eq!(feed, Feed::read_from(xml.as_bytes()).unwrap());
+    assert_eq!(feed.entries().len(), 101);
+    assert_eq!(feed.entries()[42].id(), "urn:42");
+    assert_eq!(feed.id(), "urn:feed");
+
+    let xml = std::fs::read("tests/data/feed.xml").unwrap();
+    assert_eq!(
+        Feed::read_from_parallel(xml.as_slice()).unwrap(),
+        Feed::read_from(xml.as_slice()).unwrap()
+    );
+
+    let xml =
+        r#"<feed><entry><id>urn:a</id></entry><entry><updated>yesterday</updated></entry></feed>"#;
+    assert!(matches!(
+        Feed::read_from_parallel(xml.as_bytes()),
+        Err(Error::WrongDatetime {
+            field: "updated",
+            entry: Some(1),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn read_canonicalize() {
+    let a = r#"<feed><title type="text"> Feed </title><updated>2024-01-01T01:00:00+01:00</updated>
+        <link rel="self" href="http://example.com/feed"/><link href="http://example.com/"/>
+        <author><name>B</name></author><author><name> A </name></author>
+        <entry><id> urn:a </id><published>2024-01-01T01:00:00+01:00</published><content>Body
+        </content></entry></feed>"#;
+    let b = r#"<feed><updated>2024-01-01T00:00:00Z</updated><title>Feed</title>
+        <author><name>A</name></author><author><name>B</name></author>
+        <link href="http://example.com/"/><link rel="self" href="http://example.com/feed"/>
+        <entry><id>urn:a</id><published>2024-01-01T00:00:00Z</published><content>Body</content></entry></feed>"#;
+    let mut a = Feed::read_from(a.as_bytes()).unwrap();
+    let mut b = Feed::read_from(b.as_bytes()).unwrap();
+    assert_ne!(a, b);
+
+    a.canonicalize();
+    b.canonicalize();
+    assert_eq!(a, b);
+    assert_eq!(a.to_string(), b.to_string());
+}
+
+#[test]
+fn read_effective_base() {
+    let xml = r#"<feed xml:base="http://example.com/blog/"><title xml:base="titles/">T</title>
+        <link href="feed.xml"/>
+        <entry xml:base="2024/"><id>urn:a</id><link xml:base="posts/" href="a.html"/>
+        <content xml:base="media/" type="video/mp4" src="a.mp4"/>
+        <summary>S</summary></entry>
+        <entry><id>urn:b</id><link href="/b.html"/></entry></feed>"#;
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    assert_eq!(
+        feed.title().effective_base.as_deref(),
+        Some("http://example.com/blog/titles/")
+    );
+    assert_eq!(
+        feed.links()[0].resolved_href(None),
+        "http://example.com/blog/feed.xml"
+    );
+
+    let entry = &feed.entries()[0];
+    assert_eq!(entry.base(), Some("2024/"));
+    assert_eq!(
+        entry.links()[0].resolved_href(None),
+        "http://example.com/blog/2024/posts/a.html"
+    );
+    let content = entry.content().unwrap();
+    assert_eq!(
+        content.effective_base.as_deref(),
+        Some("http://example.com/blog/2024/media/")
+    );
+    assert_eq!(
+        content.resolved_src().as_deref(),
+        Some("http://example.com/blog/2024/media/a.mp4")
+    );
+    assert_eq!(
+        entry.summary().unwrap().effective_base.as_deref(),
+        Some("http://example.com/blog/2024/")
+    );
+    assert_eq!(
+        feed.entries()[1].links()[0].resolved_href(None),
+        "http://example.com/b.html"
+    );
+
+    #[cfg(feature = "parallel")]
+    assert_eq!(Feed::read_from_parallel(xml.as_bytes()).unwrap(), feed);
+
+    let mut constructed = feed.clone();
+    constructed.title.effective_base = None;
+    assert_eq!(constructed, feed);
+    assert!(feed.to_string().contains(r#"<entry xml:base="2024/">"#));
+}
+
+#[test]
+fn read_categories_by_scheme_nested_base() {
+    let xml = r#"<feed xml:base="http://example.com/blog/"><category scheme="tags" term="a"/>
+        <entry xml:base="2024/"><id>urn:a</id><category scheme="tags" term="b"/>
+        <category xml:base="http://other.example/" scheme="tags" term="c"/></entry></feed>"#;
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    let groups = feed.categories_by_scheme();
+    let terms = |scheme: &str| {
+        groups[&Some(scheme.to_string())]
+            .iter()
+            .map(|category| category.term())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(groups.len(), 3);
+    assert_eq!(terms("http://example.com/blog/tags"), vec!["a"]);
+    assert_eq!(terms("http://example.com/blog/2024/tags"), vec!["b"]);
+    assert_eq!(terms("http://other.example/tags"), vec!["c"]);
+}
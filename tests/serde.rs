@@ -0,0 +1,36 @@
+#![cfg(all(feature = "with-serde", feature = "std"))]
+
+use atom_syndication::extension::ExtensionMap;
+use atom_syndication::Feed;
+
+#[test]
+fn serde_extension_roundtrip() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/elements/1.1/">
+        <entry>
+            <dc:creator role="editor">John Doe</dc:creator>
+            <dc:creator>Jane Doe</dc:creator>
+        </entry>
+    </feed>"#;
+    let feed = xml.parse::<Feed>().unwrap();
+
+    let json = serde_json::to_string(&feed).unwrap();
+    let deserialized = serde_json::from_str::<Feed>(&json).unwrap();
+    assert_eq!(deserialized, feed);
+
+    let extensions: &ExtensionMap = deserialized.entries()[0].extensions();
+    let creators = &extensions["dc"]["creator"];
+    assert_eq!(creators.len(), 2);
+    assert_eq!(creators[0].name(), "dc:creator");
+    assert_eq!(creators[0].value(), Some("John Doe"));
+    assert_eq!(
+        creators[0].attrs().get("role").map(String::as_str),
+        Some("editor")
+    );
+    assert_eq!(creators[1].value(), Some("Jane Doe"));
+
+    let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+    assert_eq!(
+        value["entries"][0]["extensions"]["dc"]["creator"][1]["name"],
+        "dc:creator"
+    );
+}
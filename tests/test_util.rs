@@ -0,0 +1,33 @@
+#![cfg(feature = "testing")]
+
+use std::fs::File;
+use std::io::BufReader;
+
+use atom_syndication::test_util::assert_roundtrips;
+use atom_syndication::{Feed, FeedBuilder};
+
+#[test]
+fn roundtrips() {
+    let file = File::open("tests/data/feed.xml").unwrap();
+    let feed = Feed::read_from(BufReader::new(file)).unwrap();
+    assert_roundtrips(&feed);
+}
+
+#[test]
+fn roundtrips_with_base() {
+    let mut feed = Feed::default();
+    feed.set_base("http://example.com/blog/".to_string());
+    feed.set_title("Title");
+    assert_roundtrips(&feed);
+}
+
+#[test]
+#[should_panic(expected = "feed did not roundtrip")]
+fn does_not_roundtrip() {
+    // whitespace-only extension values are read as no value
+    let feed = FeedBuilder::default()
+        .namespace(("ext".to_string(), "http://example.com".to_string()))
+        .simple_extension("ext", "name", " ")
+        .build();
+    assert_roundtrips(&feed);
+}
@@ -1,4 +1,6 @@
-use atom_syndication::{Feed, Text, TextType};
+#![cfg(feature = "std")]
+
+use atom_syndication::{Error, Feed, ParseOpts, Text, TextType};
 use std::fs::File;
 use std::io::BufReader;
 
@@ -142,3 +144,74 @@ fn text_xhtml() {
     assert_eq!(title.lang, None);
     assert_eq!(title.r#type, TextType::Xhtml);
 }
+
+#[test]
+fn text_empty_roundtrip() {
+    let xml = r#"<?xml version="1.0"?>
+<feed xmlns="http://www.w3.org/2005/Atom"><title></title><id></id><updated>1970-01-01T00:00:00+00:00</updated><rights></rights><subtitle type="html"></subtitle></feed>"#;
+    let feed = xml.parse::<Feed>().unwrap();
+    assert_eq!(feed.title(), "");
+    assert_eq!(feed.rights().map(Text::as_str), Some(""));
+    assert_eq!(feed.subtitle().map(|t| t.r#type), Some(TextType::Html));
+    assert_eq!(feed.to_string(), xml);
+}
+
+#[test]
+fn text_explicit_type_roundtrip() {
+    let xml = r#"<?xml version="1.0"?>
+<feed xmlns="http://www.w3.org/2005/Atom"><title type="text">x</title><id></id><updated>1970-01-01T00:00:00+00:00</updated><subtitle>y</subtitle></feed>"#;
+    let feed = xml.parse::<Feed>().unwrap();
+    assert!(feed.title().explicit_type);
+    assert!(!feed.subtitle().unwrap().explicit_type);
+    assert_eq!(feed.to_string(), xml);
+
+    assert!(!Text::plain("x").explicit_type);
+    let html = r#"<title type="html">x</title>"#;
+    let feed = format!("<feed>{}</feed>", html).parse::<Feed>().unwrap();
+    assert!(!feed.title().explicit_type);
+}
+
+#[test]
+fn text_type_display_roundtrip() {
+    for text_type in [TextType::Text, TextType::Html, TextType::Xhtml] {
+        assert_eq!(text_type.to_string(), text_type.as_str());
+        assert_eq!(
+            text_type.to_string().parse::<TextType>().unwrap(),
+            text_type
+        );
+    }
+}
+
+#[test]
+fn text_unknown_type() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom"><title type="application/xml">Feed Title</title></feed>"#;
+    assert!(matches!(
+        Feed::read_from(xml.as_bytes()),
+        Err(Error::WrongAttribute {
+            attribute: "type",
+            ..
+        })
+    ));
+
+    let opts = ParseOpts {
+        lenient_text_type: true,
+        ..Default::default()
+    };
+    let feed = Feed::read_from_opts(xml.as_bytes(), &opts).unwrap();
+    assert_eq!(feed.title(), "Feed Title");
+    assert_eq!(feed.title().r#type, TextType::Text);
+}
+
+#[test]
+fn text_trim_whitespace() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom"><title> Feed Title </title></feed>"#;
+    let feed = Feed::read_from(xml.as_bytes()).unwrap();
+    assert_eq!(feed.title(), " Feed Title ");
+
+    let opts = ParseOpts {
+        trim_whitespace: true,
+        ..Default::default()
+    };
+    let feed = Feed::read_from_opts(xml.as_bytes(), &opts).unwrap();
+    assert_eq!(feed.title(), "Feed Title");
+}
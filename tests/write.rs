@@ -1,9 +1,11 @@
+#![cfg(feature = "std")]
+
 extern crate atom_syndication as atom;
 
 use std::fs::File;
 use std::io::BufReader;
 
-use crate::atom::{Content, Entry, Feed};
+use crate::atom::{Content, Entry, Error, Feed, Link, Person, Text};
 
 macro_rules! feed {
     ($f:expr) => {{
@@ -37,6 +39,14 @@ fn write_generator() {
     assert_eq!(feed.to_string().parse::<Feed>().unwrap(), feed);
 }
 
+#[test]
+fn write_generator_empty() {
+    let feed = feed!("tests/data/generator_empty.xml");
+    let written = feed.to_string();
+    assert!(written.contains(r#"<generator uri="http://example.com/generator" version="1.0"/>"#));
+    assert_eq!(written.parse::<Feed>().unwrap(), feed);
+}
+
 #[test]
 fn write_link() {
     let feed = feed!("tests/data/link.xml");
@@ -61,6 +71,42 @@ fn write_extension() {
     assert_eq!(feed.to_string().parse::<Feed>().unwrap(), feed);
 }
 
+#[test]
+fn write_extension_order() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:b="http://example.com/b" xmlns:a="http://example.com/a">
+        <b:item>1</b:item>
+        <a:item>2</a:item>
+        <b:other>3</b:other>
+        <b:item>4</b:item>
+        <a:parent><a:y>5</a:y><a:x>6</a:x><a:y>7</a:y></a:parent>
+    </feed>"#;
+    let feed = xml.parse::<Feed>().unwrap();
+    let written = feed.to_string();
+    assert_eq!(
+        written,
+        "<?xml version=\"1.0\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\" xmlns:a=\"http://example.com/a\" xmlns:b=\"http://example.com/b\"><title></title><id></id><updated>1970-01-01T00:00:00+00:00</updated>\
+<a:item>2</a:item><a:parent><a:x>6</a:x><a:y>5</a:y><a:y>7</a:y></a:parent><b:item>1</b:item><b:item>4</b:item><b:other>3</b:other></feed>"
+    );
+
+    let reread = written.parse::<Feed>().unwrap();
+    assert_eq!(reread, feed);
+    assert_eq!(reread.to_string(), written);
+}
+
+#[test]
+fn write_extensions_before_entries() {
+    let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:a="http://example.com/a">
+        <entry><id>urn:entry</id><a:item>1</a:item></entry>
+        <a:item>2</a:item>
+    </feed>"#;
+    let feed = xml.parse::<Feed>().unwrap();
+    assert_eq!(
+        feed.to_string(),
+        "<?xml version=\"1.0\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\" xmlns:a=\"http://example.com/a\"><title></title><id></id><updated>1970-01-01T00:00:00+00:00</updated>\
+<a:item>2</a:item><entry><title></title><id>urn:entry</id><updated>1970-01-01T00:00:00+00:00</updated><a:item>1</a:item></entry></feed>"
+    );
+}
+
 #[test]
 fn write_content_roundtrip() {
     let mut content = Content::default();
@@ -76,3 +122,228 @@ fn write_content_roundtrip() {
 
     assert_eq!(feed.to_string().parse::<Feed>().unwrap(), feed);
 }
+
+#[test]
+fn write_enclosures_in_order() {
+    let enclosure = |href: &str| Link {
+        rel: "enclosure".to_string(),
+        href: href.to_string(),
+        ..Default::default()
+    };
+
+    let mut entry = Entry::default();
+    entry.set_links(vec![
+        enclosure("http://example.com/b.mp3"),
+        Link::default(),
+        enclosure("http://example.com/a.mp3"),
+    ]);
+
+    let mut feed = Feed::default();
+    feed.set_entries(vec![entry]);
+
+    let feed = feed.to_string().parse::<Feed>().unwrap();
+    let hrefs = feed.entries()[0]
+        .enclosures()
+        .map(Link::href)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        hrefs,
+        vec!["http://example.com/b.mp3", "http://example.com/a.mp3"]
+    );
+}
+
+#[test]
+fn write_escaping_policy() {
+    use crate::atom::extension::Extension;
+    use crate::atom::{Category, Generator};
+    use std::collections::BTreeMap;
+
+    const SPECIAL: &str = r#"a & b < c > d " e ' f"#;
+    const MARKUP: &str = "<b>a &amp; b &lt; c</b>";
+
+    let mut extension = Extension::default();
+    extension.set_name("ext:note");
+    extension.set_value(SPECIAL.to_string());
+    extension
+        .attrs
+        .insert("label".to_string(), SPECIAL.to_string());
+    let mut notes = BTreeMap::new();
+    notes.insert("note".to_string(), vec![extension]);
+
+    let person = Person {
+        name: SPECIAL.to_string(),
+        email: Some(SPECIAL.to_string()),
+        uri: Some(SPECIAL.to_string()),
+    };
+    let mut entry = Entry {
+        id: SPECIAL.to_string(),
+        title: Text::html(SPECIAL),
+        summary: Some(Text::xhtml(MARKUP)),
+        authors: vec![person.clone()],
+        categories: vec![Category {
+            term: SPECIAL.to_string(),
+            label: Some(SPECIAL.to_string()),
+            ..Default::default()
+        }],
+        links: vec![Link {
+            href: SPECIAL.to_string(),
+            title: Some(SPECIAL.to_string()),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let mut content = Content::default();
+    content.set_content_type("html".to_string());
+    content.set_value(SPECIAL.to_string());
+    entry.set_content(content);
+    entry.extensions.insert("ext".to_string(), notes);
+
+    let mut xhtml_content = Content::default();
+    xhtml_content.set_content_type("xhtml".to_string());
+    xhtml_content.set_value(MARKUP.to_string());
+    let xhtml_entry = Entry {
+        title: Text::plain(SPECIAL),
+        content: Some(xhtml_content),
+        ..Default::default()
+    };
+
+    let mut feed = Feed {
+        title: Text::plain(SPECIAL),
+        subtitle: Some(Text::xhtml(MARKUP)),
+        rights: Some(Text::html(SPECIAL)),
+        icon: Some(SPECIAL.to_string()),
+        logo: Some(SPECIAL.to_string()),
+        generator: Some(Generator {
+            value: SPECIAL.to_string(),
+            uri: Some(SPECIAL.to_string()),
+            version: Some(SPECIAL.to_string()),
+        }),
+        contributors: vec![person],
+        entries: vec![entry, xhtml_entry],
+        ..Default::default()
+    };
+    feed.namespaces
+        .insert("ext".to_string(), "urn:ext?a&b".to_string());
+
+    let written = feed.to_string();
+    assert!(!written.contains(SPECIAL));
+    assert!(written.contains("a &amp; b &lt; c &gt; d"));
+    assert_eq!(written.matches(MARKUP).count(), 3);
+    assert_eq!(written.parse::<Feed>().unwrap(), feed);
+}
+
+#[test]
+fn write_checked() {
+    let feed = feed!("tests/data/feed.xml");
+    let out = feed.write_checked(Vec::new()).unwrap();
+    assert_eq!(out, feed.write_to(Vec::new()).unwrap());
+
+    let mut entry = Entry::default();
+    entry.set_authors(vec![Person {
+        name: "John\u{1b}Doe".to_string(),
+        ..Default::default()
+    }]);
+    let mut feed = Feed::default();
+    feed.set_entries(vec![entry]);
+    match feed.write_checked(Vec::new()) {
+        Err(Error::InvalidContent { field }) => assert_eq!(field, "entry.author.name"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let mut feed = Feed::default();
+    feed.set_title(Text::xhtml("<div>unclosed"));
+    match feed.write_checked(Vec::new()) {
+        Err(Error::InvalidContent { field }) => assert_eq!(field, "title"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let mut entry = Entry::default();
+    entry.set_base("a\u{1}b".to_string());
+    let mut feed = Feed::default();
+    feed.set_entries(vec![entry]);
+    match feed.write_checked(Vec::new()) {
+        Err(Error::InvalidContent { field }) => assert_eq!(field, "entry.base"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn write_attribute_order() {
+    use crate::atom::extension::Extension;
+    use crate::atom::{Category, Generator};
+    use std::collections::BTreeMap;
+
+    let link = Link {
+        href: "http://example.com/a.mp3".to_string(),
+        rel: "enclosure".to_string(),
+        hreflang: Some("en".to_string()),
+        mime_type: Some("audio/mpeg".to_string()),
+        title: Some("Audio".to_string()),
+        length: Some("1024".to_string()),
+        base: Some("http://example.com/".to_string()),
+        effective_base: None,
+    };
+    let category = Category {
+        term: "technology".to_string(),
+        scheme: Some("http://example.com/tags".to_string()),
+        label: Some("Technology".to_string()),
+        base: Some("http://example.com/".to_string()),
+    };
+    let generator = Generator {
+        value: "Example".to_string(),
+        uri: Some("http://example.com/generator".to_string()),
+        version: Some("1.0".to_string()),
+    };
+    let mut subtitle = Text::html("<b>Subtitle</b>");
+    subtitle.base = Some("http://example.com/".to_string());
+    subtitle.lang = Some("en".to_string());
+    let content = Content {
+        base: Some("http://example.com/".to_string()),
+        lang: Some("en".to_string()),
+        content_type: Some("audio/mpeg".to_string()),
+        src: Some("a.mp3".to_string()),
+        ..Default::default()
+    };
+    let mut attrs = BTreeMap::new();
+    attrs.insert("z".to_string(), "1".to_string());
+    attrs.insert("a".to_string(), "2".to_string());
+    let extension = Extension {
+        name: "ext:item".to_string(),
+        attrs,
+        ..Default::default()
+    };
+
+    let mut entry = Entry::default();
+    entry.set_xml_id(Some("entry-1".to_string()));
+    entry.set_base(Some("http://example.com/2024/".to_string()));
+    entry.set_content(content);
+    let mut feed = Feed::default();
+    feed.set_xml_id(Some("feed".to_string()));
+    feed.set_lang(Some("en".to_string()));
+    feed.set_base(Some("http://example.com/".to_string()));
+    feed.namespaces
+        .insert("ext".to_string(), "http://example.com/ext".to_string());
+    feed.set_links(vec![link]);
+    feed.set_categories(vec![category]);
+    feed.set_generator(generator);
+    feed.set_subtitle(subtitle);
+    feed.extensions
+        .entry("ext".to_string())
+        .or_default()
+        .insert("item".to_string(), vec![extension]);
+    feed.set_entries(vec![entry]);
+
+    let xml = feed.to_string();
+    for expected in [
+        r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:ext="http://example.com/ext" xml:base="http://example.com/" xml:lang="en" xml:id="feed">"#,
+        r#"<link xml:base="http://example.com/" href="http://example.com/a.mp3" rel="enclosure" type="audio/mpeg" hreflang="en" title="Audio" length="1024"/>"#,
+        r#"<category xml:base="http://example.com/" term="technology" scheme="http://example.com/tags" label="Technology"/>"#,
+        r#"<generator uri="http://example.com/generator" version="1.0">"#,
+        r#"<subtitle xml:base="http://example.com/" xml:lang="en" type="html">"#,
+        r#"<entry xml:base="http://example.com/2024/" xml:id="entry-1">"#,
+        r#"<content xml:base="http://example.com/" xml:lang="en" type="audio/mpeg" src="a.mp3">"#,
+        r#"<ext:item a="2" z="1">"#,
+    ] {
+        assert!(xml.contains(expected), "{} not found in {}", expected, xml);
+    }
+}